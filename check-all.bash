@@ -9,4 +9,5 @@ for task in doc clippy check test; do
   just $task --no-default-features "$@"
   just $task --all-features "$@"
   just $task --features std "$@"
+  just $task --workspace "$@"
 done
@@ -1,9 +1,9 @@
 //! This example demonstrates dynamically and lazily loading expensive resources into string interpolations of [`lazy_template::simple_curly_braces`].
 
 use derive_more::Display;
-use lazy_template::simple_curly_braces;
+use lazy_template::{responders, simple_curly_braces};
 use pipe_trait::Pipe;
-use std::{borrow::Cow, env, fs, io, process::ExitCode};
+use std::{borrow::Cow, env, process::ExitCode};
 use text_block_macros::text_block;
 
 static HELP: &str = text_block! {
@@ -33,12 +33,9 @@ struct DisplayMessage<'a>(Cow<'a, str>);
 
 #[derive(Debug, Display)]
 enum ErrorMessage<'a> {
-    #[display("Positional argument at {_0} is not provided")]
-    Arg(usize),
-    #[display("Cannot read environment variable '{_0}': {_1}")]
-    Env(&'a str, env::VarError),
-    #[display("Failed to read file '{_0}': {_1}")]
-    File(&'a str, io::Error),
+    Arg(responders::MissingPositional),
+    Env(responders::EnvError),
+    File(responders::FileError),
     #[display("Unsupported query: {_0}")]
     Query(&'a str),
 }
@@ -52,36 +49,32 @@ fn main() -> ExitCode {
     }
 
     let template_str = &args[1];
-    let mut pos_args = args[2..].iter();
-    let mut pos_index = 0;
+    let mut pos_args = responders::positional(&args[2..]);
+    let mut env_var = responders::from_env();
+    let mut file = responders::from_file();
 
     let system = simple_curly_braces();
     let template = system.lazy_parse(template_str);
 
     let result = template.to_string(|query| -> Result<DisplayMessage, ErrorMessage> {
         if is_positional(query) {
-            let result = pos_args
-                .next()
-                .ok_or(ErrorMessage::Arg(pos_index))
+            return pos_args(query)
+                .map_err(ErrorMessage::Arg)
                 .map(String::as_str)
                 .map(Cow::Borrowed)
                 .map(DisplayMessage);
-            pos_index += 1;
-            return result;
         }
 
         if let Some(env) = parse_env_var(query) {
-            return env
-                .pipe(env::var)
-                .map_err(|error| ErrorMessage::Env(env, error))
+            return env_var(env)
+                .map_err(ErrorMessage::Env)
                 .map(Cow::Owned)
                 .map(DisplayMessage);
         }
 
-        if let Some(file) = parse_file_path(query) {
-            return file
-                .pipe(fs::read_to_string)
-                .map_err(|error| ErrorMessage::File(file, error))
+        if let Some(path) = parse_file_path(query) {
+            return file(path)
+                .map_err(ErrorMessage::File)
                 .map(Cow::Owned)
                 .map(DisplayMessage);
         }
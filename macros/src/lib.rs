@@ -0,0 +1,237 @@
+//! Procedural macros for [lazy-template](https://docs.rs/lazy-template).
+use lazy_template::{enclosed::Segment, simple_curly_braces, GetSpan, Position, Span};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Parse a template string of the [`simple_curly_braces`](lazy_template::simple_curly_braces()) syntax at compile time.
+///
+/// The macro expands to a `ParsedTemplate` whose segments are stored in a static array, so no parsing happens at
+/// runtime:
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template_macros::lazy_template;
+/// let parsed_template = lazy_template!("{name} is {age} years old");
+/// let output = parsed_template
+///     .to_template()
+///     .to_string(|query| match query {
+///         "name" => Ok("Alice"),
+///         "age" => Ok("20"),
+///         _ => Err(format!("Can't answer {query:?}")),
+///     })
+///     .unwrap();
+/// assert_eq!(output, "Alice is 20 years old");
+/// ```
+///
+/// Syntax errors are reported as compile errors:
+///
+/// ```compile_fail
+/// let parsed_template = lazy_template_macros::lazy_template!("{name} is {age years old");
+/// ```
+#[proc_macro]
+pub fn lazy_template(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    expand(&literal)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(literal: &LitStr) -> syn::Result<TokenStream2> {
+    let text = literal.value();
+    let system = simple_curly_braces();
+    let segments = system
+        .eager_parse::<Vec<_>>(&text)
+        .map_err(|error| {
+            let message = format!("Invalid template at {}: {error}", error.span());
+            syn::Error::new(literal.span(), message)
+        })?
+        .segments()
+        .map(segment_tokens)
+        .collect::<Vec<_>>();
+    let count = segments.len();
+
+    Ok(quote! {{
+        static SEGMENTS: [::lazy_template::enclosed::Segment<&'static str, &'static str>; #count] =
+            [#(#segments),*];
+        ::lazy_template::iter::ParsedTemplate::<_, &'static str>::new(&SEGMENTS as &'static [_])
+    }})
+}
+
+fn segment_tokens(segment: &Segment<&str, &str>) -> TokenStream2 {
+    let span = span_tokens(segment.span());
+    match segment {
+        Segment::Character(char, _) => {
+            quote! { ::lazy_template::enclosed::Segment::Character(#char, #span) }
+        }
+        Segment::Text(text, _) => {
+            quote! { ::lazy_template::enclosed::Segment::Text(#text, #span) }
+        }
+        Segment::Expression(query, _) => {
+            quote! { ::lazy_template::enclosed::Segment::Expression(#query, #span) }
+        }
+    }
+}
+
+fn span_tokens(Span { start, end }: Span) -> TokenStream2 {
+    let start = position_tokens(start);
+    let end = position_tokens(end);
+    quote! { ::lazy_template::Span { start: #start, end: #end } }
+}
+
+fn position_tokens(
+    Position {
+        offset,
+        line,
+        column,
+    }: Position,
+) -> TokenStream2 {
+    quote! {
+        ::lazy_template::Position {
+            offset: #offset,
+            line: #line,
+            column: #column,
+        }
+    }
+}
+
+/// Derive `lazy_template::RespondFields` for a struct with named fields.
+///
+/// Each query is answered by the field of the same name, whose type must implement `Display`. The field attributes
+/// are:
+/// * `#[respond(rename = "...")]`: answer a different query name.
+/// * `#[respond(nested)]`: answer the queries prefixed with the field name and a dot (e.g. `address.city`) with the
+///   field, whose type must implement `RespondFields`.
+/// * `#[respond(skip)]`: do not answer any query with the field.
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{simple_curly_braces, RespondFields};
+/// use lazy_template_macros::RespondFields;
+///
+/// #[derive(RespondFields)]
+/// struct Address {
+///     city: String,
+/// }
+///
+/// #[derive(RespondFields)]
+/// struct Person {
+///     name: String,
+///     #[respond(rename = "years")]
+///     age: u32,
+///     #[respond(nested)]
+///     address: Address,
+/// }
+///
+/// let alice = Person {
+///     name: "Alice".to_string(),
+///     age: 20,
+///     address: Address {
+///         city: "Hanoi".to_string(),
+///     },
+/// };
+/// let output = simple_curly_braces()
+///     .lazy_parse("{name} ({years}) lives in {address.city}")
+///     .to_string(alice.responder())
+///     .unwrap();
+/// assert_eq!(output, "Alice (20) lives in Hanoi");
+/// let error = simple_curly_braces()
+///     .lazy_parse("{address.country}")
+///     .to_string(alice.responder())
+///     .unwrap_err();
+/// assert_eq!(error.to_string(), "Unknown query \"address.country\"");
+/// ```
+#[proc_macro_derive(RespondFields, attributes(respond))]
+pub fn derive_respond_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_respond_fields(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// How a field answers queries.
+enum FieldKind {
+    Value,
+    Nested,
+    Skip,
+}
+
+fn expand_respond_fields(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                let message = "RespondFields can only be derived for structs with named fields";
+                return Err(syn::Error::new_spanned(&data.fields, message));
+            }
+        },
+        _ => {
+            let message = "RespondFields can only be derived for structs";
+            return Err(syn::Error::new_spanned(&input.ident, message));
+        }
+    };
+
+    let mut value_arms = Vec::new();
+    let mut nested_arms = Vec::new();
+    for field in fields {
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("named fields should have identifiers");
+        let mut name = ident.to_string();
+        let mut kind = FieldKind::Value;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("respond"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.path.is_ident("nested") {
+                    kind = FieldKind::Nested;
+                } else if meta.path.is_ident("skip") {
+                    kind = FieldKind::Skip;
+                } else {
+                    return Err(meta.error("expected `rename`, `nested`, or `skip`"));
+                }
+                Ok(())
+            })?;
+        }
+        match kind {
+            FieldKind::Value => value_arms.push(quote! {
+                #name => ::core::result::Result::Ok(&self.#ident),
+            }),
+            FieldKind::Nested => nested_arms.push(quote! {
+                #name => ::lazy_template::RespondFields::respond(&self.#ident, tail),
+            }),
+            FieldKind::Skip => {}
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::lazy_template::RespondFields for #ident #type_generics #where_clause {
+            fn respond(
+                &self,
+                query: &str,
+            ) -> ::core::result::Result<&dyn ::core::fmt::Display, ::lazy_template::UnknownQuery> {
+                let unknown_query = || ::lazy_template::UnknownQuery::new(query);
+                match query {
+                    #(#value_arms)*
+                    _ => match query.split_once('.') {
+                        #[allow(unused_variables)]
+                        ::core::option::Option::Some((head, tail)) => match head {
+                            #(#nested_arms)*
+                            _ => ::core::result::Result::Err(unknown_query()),
+                        }
+                        .map_err(|_| unknown_query()),
+                        ::core::option::Option::None => ::core::result::Result::Err(unknown_query()),
+                    },
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,115 @@
+use lazy_template::{simple_curly_braces, RespondFields, TemplateApplicationError, UnknownQuery};
+use lazy_template_macros::RespondFields;
+use pretty_assertions::assert_eq;
+use std::fmt::Display;
+
+#[derive(RespondFields)]
+struct Country {
+    name: &'static str,
+    code: &'static str,
+}
+
+#[derive(RespondFields)]
+struct Address {
+    city: String,
+    #[respond(nested)]
+    country: Country,
+}
+
+#[derive(RespondFields)]
+struct Person {
+    #[respond(rename = "first-name")]
+    first_name: String,
+    age: u8,
+    #[respond(skip)]
+    #[allow(dead_code)]
+    password: String,
+    #[respond(nested, rename = "home")]
+    address: Address,
+}
+
+#[derive(RespondFields)]
+struct Labeled<Value: Display> {
+    label: &'static str,
+    value: Value,
+}
+
+fn alice() -> Person {
+    Person {
+        first_name: "Alice".to_string(),
+        age: 20,
+        password: "hunter2".to_string(),
+        address: Address {
+            city: "Hanoi".to_string(),
+            country: Country {
+                name: "Vietnam",
+                code: "VN",
+            },
+        },
+    }
+}
+
+#[test]
+fn fields() {
+    let alice = alice();
+    let output = simple_curly_braces()
+        .lazy_parse("{first-name} is {age}")
+        .to_string(alice.responder())
+        .unwrap();
+    assert_eq!(output, "Alice is 20");
+}
+
+#[test]
+fn nested_paths() {
+    let alice = alice();
+    let system = simple_curly_braces();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>("{home.city}, {home.country.name} ({home.country.code})")
+        .unwrap();
+    let output = parsed_template
+        .to_template()
+        .to_string(alice.responder())
+        .unwrap();
+    assert_eq!(output, "Hanoi, Vietnam (VN)");
+}
+
+#[test]
+fn unknown_queries() {
+    let alice = alice();
+    let unknown = |template: &str| {
+        let error = simple_curly_braces()
+            .lazy_parse(template)
+            .to_string(alice.responder())
+            .unwrap_err();
+        match error {
+            TemplateApplicationError::Query(error) => error,
+            error => panic!("expecting a query error, got {error:?}"),
+        }
+    };
+    assert_eq!(unknown("{first_name}"), UnknownQuery::new("first_name"));
+    assert_eq!(unknown("{password}"), UnknownQuery::new("password"));
+    assert_eq!(unknown("{address.city}"), UnknownQuery::new("address.city"));
+    assert_eq!(unknown("{home.zip}"), UnknownQuery::new("home.zip"));
+    assert_eq!(
+        unknown("{home.country.flag}"),
+        UnknownQuery::new("home.country.flag")
+    );
+    assert_eq!(unknown("{age.value}"), UnknownQuery::new("age.value"));
+    assert_eq!(
+        unknown("{home.country.flag}").to_string(),
+        "Unknown query \"home.country.flag\"",
+    );
+}
+
+#[test]
+fn generic_struct() {
+    let labeled = Labeled {
+        label: "ratio",
+        value: 1.5,
+    };
+    let output = simple_curly_braces()
+        .lazy_parse("{label} = {value}")
+        .to_string(labeled.responder())
+        .unwrap();
+    assert_eq!(output, "ratio = 1.5");
+}
@@ -0,0 +1,56 @@
+use lazy_template::{enclosed::Segment, iter::ParsedTemplate, simple_curly_braces, GetSpan};
+use lazy_template_macros::lazy_template;
+use pretty_assertions::assert_eq;
+
+type StaticParsedTemplate =
+    ParsedTemplate<&'static [Segment<&'static str, &'static str>], &'static str>;
+
+static GREETING: StaticParsedTemplate = lazy_template!("Hello, {name}!");
+
+fn respond(query: &str) -> Result<&'static str, String> {
+    match query {
+        "name" => Ok("Alice"),
+        "age" => Ok("20"),
+        _ => Err(format!("Can't answer {query:?}")),
+    }
+}
+
+#[test]
+fn render() {
+    let output = lazy_template!("{name} is {age} years old \\{age\\}\\n")
+        .to_template()
+        .to_string(respond)
+        .unwrap();
+    assert_eq!(output, "Alice is 20 years old {age}\n");
+}
+
+#[test]
+fn static_template() {
+    let output = GREETING.to_template().to_string(respond).unwrap();
+    assert_eq!(output, "Hello, Alice!");
+}
+
+#[test]
+fn same_as_runtime_parsing() {
+    let template = "{name}\n is \\\\{age}é";
+    let compiled = lazy_template!("{name}\n is \\\\{age}é");
+    let system = simple_curly_braces();
+    let parsed = system.eager_parse::<Vec<_>>(template).unwrap();
+    let spans = |segments: &[Segment<&str, &str>]| -> Vec<_> {
+        segments.iter().map(GetSpan::span).collect()
+    };
+    assert_eq!(
+        spans(compiled.segments().as_slice()),
+        spans(parsed.segments().as_slice())
+    );
+    assert_eq!(
+        compiled.to_template().to_string(respond).unwrap(),
+        parsed.to_template().to_string(respond).unwrap(),
+    );
+}
+
+#[test]
+fn empty() {
+    let output = lazy_template!("").to_template().to_string(respond).unwrap();
+    assert_eq!(output, "");
+}
@@ -0,0 +1,159 @@
+use clap::{builder::NonEmptyStringValueParser, Args, Parser, Subcommand, ValueEnum};
+use lazy_template::{
+    enclosed::{
+        self, ConfigError, ParserConfig, SimpleEnclosedTemplateSystem, SimpleEscapeParser,
+        SimpleQueryParser,
+    },
+    IntoTemplateSystem,
+};
+use std::path::PathBuf;
+use text_block_macros::text_block;
+
+const RENDER_HELP: &str = text_block! {
+    "Query Syntax:"
+    "  {}           The next positional argument after the options"
+    "  {env:NAME}   The environment variable NAME"
+    "  {file:PATH}  The content of the text file at PATH"
+    "  {KEY}        The value of KEY from --var or --data"
+    ""
+    "Exit Codes:"
+    "  1  Failed to read the template or the data files"
+    "  2  Invalid command-line arguments"
+    "  3  Failed to parse the template"
+    "  4  Failed to answer a query"
+    "  5  Failed to write the output"
+    ""
+    "Examples:"
+    "  lazy-template render --template 'Hello, {}!' Alice"
+    "  lazy-template render --template 'Home directory: {env:HOME}'"
+    "  lazy-template render --template-file greeting.txt --var name=Alice --data config.toml"
+    "  echo 'Hello, <<name>>!' | lazy-template render --open '<<' --close '>>' --var name=Alice"
+};
+
+const CHECK_HELP: &str = text_block! {
+    "Allow-list:"
+    "  Without --allow and --allow-file, only the syntax of the templates is checked."
+    "  An allowed query that ends with '*' allows every query that starts with the part before '*'."
+    ""
+    "Exit Codes:"
+    "  0  No problems were found"
+    "  1  Failed to read the templates or the allow-list"
+    "  2  Invalid command-line arguments"
+    "  3  Some templates failed to parse"
+    "  4  Some templates contain disallowed queries (but no parse errors)"
+    ""
+    "Examples:"
+    "  lazy-template check templates/*.txt"
+    "  lazy-template check --allow name --allow 'env:*' --format json greeting.txt"
+};
+
+/// Render and check string templates whose queries are answered by the command-line arguments, the environment, and
+/// data files.
+#[derive(Debug, Parser)]
+#[clap(name = "lazy-template", version)]
+pub struct CliArgs {
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Render a template and write the result to the standard output.
+    #[clap(after_help = RENDER_HELP)]
+    Render(RenderArgs),
+    /// Check the syntax and the queries of template files without rendering them.
+    #[clap(after_help = CHECK_HELP)]
+    Check(CheckArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct RenderArgs {
+    /// Template string to render.
+    ///
+    /// The template is read from the standard input if neither --template nor --template-file is provided.
+    #[clap(long, short, conflicts_with = "template_file")]
+    pub template: Option<String>,
+
+    /// File to read the template from.
+    #[clap(long, short = 'f', value_name = "PATH")]
+    pub template_file: Option<PathBuf>,
+
+    /// Answer the query KEY with VALUE (overrides the values from --data).
+    #[clap(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+    pub vars: Vec<(String, String)>,
+
+    /// JSON or TOML file whose values answer the queries, such as `{user.name}` or `{items.0}`.
+    #[clap(long = "data", value_name = "PATH")]
+    pub data_files: Vec<PathBuf>,
+
+    #[clap(flatten)]
+    pub delimiters: DelimiterArgs,
+
+    /// Answers of the `{}` queries, in order.
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct CheckArgs {
+    /// Only allow the queries in this list (can be repeated).
+    #[clap(long = "allow", value_name = "QUERY")]
+    pub allow: Vec<String>,
+
+    /// Only allow the queries listed in this file, one per line (can be repeated).
+    #[clap(long = "allow-file", value_name = "PATH")]
+    pub allow_files: Vec<PathBuf>,
+
+    /// Format of the diagnostics.
+    #[clap(long, value_enum, default_value_t = Format::Human)]
+    pub format: Format,
+
+    #[clap(flatten)]
+    pub delimiters: DelimiterArgs,
+
+    /// Template files to check.
+    #[clap(required = true)]
+    pub files: Vec<PathBuf>,
+}
+
+/// Format of the diagnostics of the `check` subcommand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// One line per diagnostic, in the form of `FILE:LINE:COLUMN: MESSAGE`.
+    Human,
+    /// A JSON array of diagnostics.
+    Json,
+}
+
+#[derive(Debug, Args)]
+pub struct DelimiterArgs {
+    /// Opening delimiter of the queries.
+    #[clap(long, default_value = "{", value_parser = NonEmptyStringValueParser::new())]
+    pub open: String,
+
+    /// Closing delimiter of the queries.
+    #[clap(long, default_value = "}", value_parser = NonEmptyStringValueParser::new())]
+    pub close: String,
+}
+
+impl DelimiterArgs {
+    /// Create the [`ParserConfig`] of the delimiters.
+    pub fn into_config(self) -> Result<ParserConfig, ConfigError> {
+        ParserConfig::new(self.open, self.close)
+    }
+
+    /// Create the template system of the delimiters.
+    pub fn into_template_system<'a>(self) -> Result<SimpleEnclosedTemplateSystem<'a>, ConfigError> {
+        let system = enclosed::Parser::curly_braces()
+            .with_config(self.into_config()?)
+            .with_escape_parser(SimpleEscapeParser)
+            .with_query_parser(SimpleQueryParser)
+            .into_template_system();
+        Ok(system)
+    }
+}
+
+fn parse_var(text: &str) -> Result<(String, String), String> {
+    text.split_once('=')
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or_else(|| format!("{text:?} is not in the form of KEY=VALUE"))
+}
@@ -0,0 +1,97 @@
+use crate::{
+    args::{CheckArgs, Format},
+    failure::Failure,
+};
+use lazy_template::{Diagnostic, GetSpan};
+use serde_json::json;
+use std::fs;
+
+/// Queries that are allowed by the `--allow` and `--allow-file` options.
+struct AllowList(Vec<String>);
+
+impl AllowList {
+    fn contains(&self, query: &str) -> bool {
+        self.0
+            .iter()
+            .any(|allowed| match allowed.strip_suffix('*') {
+                Some(prefix) => query.starts_with(prefix),
+                None => query == allowed,
+            })
+    }
+}
+
+pub fn check(args: CheckArgs) -> Result<(), Failure> {
+    let CheckArgs {
+        mut allow,
+        allow_files,
+        format,
+        delimiters,
+        files,
+    } = args;
+
+    let allow_list = if allow.is_empty() && allow_files.is_empty() {
+        None
+    } else {
+        for path in &allow_files {
+            let content = fs::read_to_string(path).map_err(|error| Failure::read(path, error))?;
+            let lines = content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty());
+            allow.extend(lines.map(str::to_string));
+        }
+        Some(AllowList(allow))
+    };
+
+    let system = delimiters.into_template_system().map_err(Failure::Config)?;
+    let mut parse_errors = 0;
+    let mut disallowed_queries = 0;
+    let mut reports = Vec::new();
+    for path in &files {
+        let template = fs::read_to_string(path).map_err(|error| Failure::read(path, error))?;
+        let diagnostics: Vec<_> = system.check(&template, |query| {
+            allow_list
+                .as_ref()
+                .is_none_or(|allow_list| allow_list.contains(query))
+        });
+        for diagnostic in diagnostics {
+            let span = diagnostic.span();
+            let message = diagnostic.to_string();
+            let (kind, query) = match diagnostic {
+                Diagnostic::Parse(_) => {
+                    parse_errors += 1;
+                    ("parse", None)
+                }
+                Diagnostic::DisallowedQuery(query, _) => {
+                    disallowed_queries += 1;
+                    ("disallowed-query", Some(query))
+                }
+            };
+            let file = path.display();
+            match format {
+                Format::Human => println!("{file}:{span}: {message}"),
+                Format::Json => reports.push(json!({
+                    "file": file.to_string(),
+                    "kind": kind,
+                    "message": message,
+                    "query": query,
+                    "start": { "line": span.start.line, "column": span.start.column, "offset": span.start.offset },
+                    "end": { "line": span.end.line, "column": span.end.column, "offset": span.end.offset },
+                })),
+            }
+        }
+    }
+
+    if format == Format::Json {
+        println!("{}", serde_json::Value::Array(reports));
+    }
+
+    if parse_errors == 0 && disallowed_queries == 0 {
+        Ok(())
+    } else {
+        Err(Failure::Check {
+            parse_errors,
+            disallowed_queries,
+        })
+    }
+}
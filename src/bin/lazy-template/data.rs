@@ -0,0 +1,94 @@
+use crate::failure::Failure;
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Answers of the named queries.
+pub type Data = BTreeMap<String, String>;
+
+/// Load a JSON or TOML file (determined by its extension) into `data`.
+///
+/// Nested values are flattened with dots, so `{"user": {"name": "Alice"}}` answers the query `user.name`, and
+/// `{"items": ["a", "b"]}` answers the queries `items.0` and `items.1`. JSON `null` values are treated as absent.
+pub fn load(path: &Path, data: &mut Data) -> Result<(), Failure> {
+    let text = fs::read_to_string(path).map_err(|error| Failure::read(path, error))?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => {
+            let value = serde_json::from_str(&text).map_err(|error| Failure::data(path, error))?;
+            flatten_json(String::new(), value, data);
+        }
+        Some("toml") => {
+            let table = toml::from_str(&text).map_err(|error| Failure::data(path, error))?;
+            flatten_toml(String::new(), toml::Value::Table(table), data);
+        }
+        _ => {
+            return Err(Failure::data(
+                path,
+                "Unknown data format, the extension should be either .json or .toml",
+            ))
+        }
+    }
+    Ok(())
+}
+
+fn join_key(prefix: &str, key: impl AsRef<str>) -> String {
+    match prefix {
+        "" => key.as_ref().to_string(),
+        _ => format!("{prefix}.{}", key.as_ref()),
+    }
+}
+
+fn flatten_json(key: String, value: serde_json::Value, data: &mut Data) {
+    use serde_json::Value;
+    match value {
+        Value::Null => {}
+        Value::Bool(value) => {
+            data.insert(key, value.to_string());
+        }
+        Value::Number(value) => {
+            data.insert(key, value.to_string());
+        }
+        Value::String(value) => {
+            data.insert(key, value);
+        }
+        Value::Array(items) => {
+            for (index, item) in items.into_iter().enumerate() {
+                flatten_json(join_key(&key, index.to_string()), item, data);
+            }
+        }
+        Value::Object(entries) => {
+            for (name, value) in entries {
+                flatten_json(join_key(&key, name), value, data);
+            }
+        }
+    }
+}
+
+fn flatten_toml(key: String, value: toml::Value, data: &mut Data) {
+    use toml::Value;
+    match value {
+        Value::Boolean(value) => {
+            data.insert(key, value.to_string());
+        }
+        Value::Integer(value) => {
+            data.insert(key, value.to_string());
+        }
+        Value::Float(value) => {
+            data.insert(key, value.to_string());
+        }
+        Value::Datetime(value) => {
+            data.insert(key, value.to_string());
+        }
+        Value::String(value) => {
+            data.insert(key, value);
+        }
+        Value::Array(items) => {
+            for (index, item) in items.into_iter().enumerate() {
+                flatten_toml(join_key(&key, index.to_string()), item, data);
+            }
+        }
+        Value::Table(entries) => {
+            for (name, value) in entries {
+                flatten_toml(join_key(&key, name), value, data);
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use derive_more::Display;
+use lazy_template::enclosed::ConfigError;
+use std::{fmt, io, path::Path, process::ExitCode};
+
+/// Reasons for the CLI to exit with a non-zero status.
+#[derive(Debug, Display)]
+pub enum Failure {
+    #[display("Invalid delimiters: {_0}")]
+    Config(ConfigError),
+    #[display("Failed to read {path}: {error}")]
+    Read { path: String, error: io::Error },
+    #[display("Invalid data file {path}: {message}")]
+    Data { path: String, message: String },
+    #[display("{}", _0.join("\n"))]
+    Parse(Vec<String>),
+    #[display("{_0}")]
+    Query(String),
+    #[display("Failed to write the output: {_0}")]
+    Write(io::Error),
+    #[display(
+        "Found {parse_errors} parse error(s) and {disallowed_queries} disallowed query(ies)"
+    )]
+    Check {
+        parse_errors: usize,
+        disallowed_queries: usize,
+    },
+}
+
+impl Failure {
+    pub fn read(path: &Path, error: io::Error) -> Self {
+        Failure::Read {
+            path: path.display().to_string(),
+            error,
+        }
+    }
+
+    pub fn data(path: &Path, message: impl fmt::Display) -> Self {
+        Failure::Data {
+            path: path.display().to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Each kind of failure has a distinct exit code so that scripts can tell them apart.
+    ///
+    /// The exit code `2` is reserved for invalid command-line arguments, most of which are reported by `clap`.
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            Failure::Config(_) => ExitCode::from(2),
+            Failure::Read { .. } | Failure::Data { .. } => ExitCode::from(1),
+            Failure::Parse(_) => ExitCode::from(3),
+            Failure::Check { parse_errors, .. } if *parse_errors > 0 => ExitCode::from(3),
+            Failure::Query(_) | Failure::Check { .. } => ExitCode::from(4),
+            Failure::Write(_) => ExitCode::from(5),
+        }
+    }
+
+    /// Print the failure to the standard error, one line per error.
+    pub fn report(&self) {
+        match self {
+            Failure::Parse(errors) => {
+                for error in errors {
+                    eprintln!("error: {error}");
+                }
+            }
+            _ => eprintln!("error: {self}"),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+mod args;
+mod check;
+mod data;
+mod failure;
+mod render;
+
+use args::{CliArgs, Command};
+use clap::Parser;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let CliArgs { command } = CliArgs::parse();
+    let result = match command {
+        Command::Render(args) => render::render(args),
+        Command::Check(args) => check::check(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            failure.report();
+            failure.exit_code()
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    args::RenderArgs,
+    data::{self, Data},
+    failure::Failure,
+};
+use derive_more::Display;
+use lazy_template::{
+    responders::{self, EnvError, FileError, MissingPositional},
+    GetSpan, ResponderExt, TemplateApplicationError, UnknownQuery,
+};
+use pipe_trait::Pipe;
+use std::{
+    fs,
+    io::{self, Read, Write},
+};
+
+/// Errors of the respond function of the `render` subcommand.
+#[derive(Debug, Display)]
+enum QueryError {
+    Positional(MissingPositional),
+    Env(EnvError),
+    File(FileError),
+    Unknown(UnknownQuery),
+}
+
+pub fn render(args: RenderArgs) -> Result<(), Failure> {
+    let RenderArgs {
+        template,
+        template_file,
+        vars,
+        data_files,
+        delimiters,
+        args,
+    } = args;
+
+    let (source, template) = match (template, template_file) {
+        (Some(template), _) => ("<template>".to_string(), template),
+        (None, Some(path)) => {
+            let template =
+                fs::read_to_string(&path).map_err(|error| Failure::read(&path, error))?;
+            (path.display().to_string(), template)
+        }
+        (None, None) => {
+            let mut template = String::new();
+            io::stdin()
+                .read_to_string(&mut template)
+                .map_err(|error| Failure::Read {
+                    path: "<stdin>".to_string(),
+                    error,
+                })?;
+            ("<stdin>".to_string(), template)
+        }
+    };
+
+    let mut data = Data::new();
+    for path in &data_files {
+        data::load(path, &mut data)?;
+    }
+    data.extend(vars);
+
+    let system = delimiters.into_template_system().map_err(Failure::Config)?;
+    let (parsed_template, errors) = system.recovering_parse::<Vec<_>, Vec<_>>(&template);
+    if !errors.is_empty() {
+        return errors
+            .iter()
+            .map(|error| format!("{source}:{}: {error}", error.span()))
+            .collect::<Vec<_>>()
+            .pipe(Failure::Parse)
+            .pipe(Err);
+    }
+
+    let mut positional = responders::positional(&args)
+        .map_output(String::clone)
+        .map_err(QueryError::Positional);
+    let mut named = responders::from_map(&data)
+        .map_output(String::clone)
+        .map_err(QueryError::Unknown)
+        .route_prefix("env:", responders::from_env().map_err(QueryError::Env))
+        .route_prefix("file:", responders::from_file().map_err(QueryError::File));
+
+    // the output is only written after the whole template is rendered so that failures do not leave partial output
+    let output = parsed_template
+        .to_template()
+        .to_string(|query| match query {
+            "" => positional(query),
+            _ => named(query),
+        })
+        .map_err(|error| match error {
+            TemplateApplicationError::Parse(error) => match error {},
+            TemplateApplicationError::Query(error) => Failure::Query(error.to_string()),
+            TemplateApplicationError::Write(error) => Failure::Write(io::Error::other(error)),
+        })?;
+    let mut stdout = io::stdout().lock();
+    stdout
+        .write_all(output.as_bytes())
+        .and_then(|()| stdout.flush())
+        .map_err(Failure::Write)
+}
@@ -0,0 +1,85 @@
+use crate::{iter::RecoveringParseIter, GetQuery, GetSpan, Recover, Span, TemplateSystem};
+use derive_more::Display;
+
+/// A problem found by [`TemplateSystem::check`].
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Diagnostic<ParseError, Query> {
+    /// A segment of the template failed to parse.
+    #[display("{_0}")]
+    Parse(ParseError),
+    /// A query of the template is not in the allow-list.
+    #[display("Query {_0:?} is not allowed")]
+    DisallowedQuery(Query, Span),
+}
+
+impl<ParseError, Query> GetSpan for Diagnostic<ParseError, Query>
+where
+    ParseError: GetSpan,
+{
+    fn span(&self) -> Span {
+        match self {
+            Diagnostic::Parse(error) => error.span(),
+            Diagnostic::DisallowedQuery(_, span) => *span,
+        }
+    }
+}
+
+impl<'a, Parser, Query> TemplateSystem<Parser, Query>
+where
+    Parser: Recover<'a>,
+    Parser::Output: GetQuery + GetSpan,
+{
+    /// Validate a template string without rendering it.
+    ///
+    /// Every parse error is reported, and every query for which `is_allowed` returns `false` is reported as a
+    /// [disallowed query](Diagnostic::DisallowedQuery). The diagnostics are in the order of their positions in the
+    /// template:
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "std"))] fn main() {}
+    /// # #[cfg(feature = "std")] fn main() {
+    /// # use pretty_assertions::assert_eq;
+    /// use lazy_template::GetSpan;
+    /// let system = lazy_template::simple_curly_braces();
+    /// let allow_list = ["name", "age"];
+    /// let diagnostics: Vec<_> = system.check("Hello, {name}!\n{}} {password}", |query| {
+    ///     allow_list.contains(query)
+    /// });
+    /// let diagnostics: Vec<_> = diagnostics
+    ///     .iter()
+    ///     .map(|diagnostic| format!("{}: {diagnostic}", diagnostic.span()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     diagnostics,
+    ///     [
+    ///         "2:1: Query \"\" is not allowed",
+    ///         "2:3: Failed to parse query: Unexpected token \"}\"",
+    ///         "2:5: Query \"password\" is not allowed",
+    ///     ],
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// To only check the syntax, allow every query with `|_| true`.
+    pub fn check<DiagnosticContainer, IsAllowed>(
+        &'a self,
+        text: &'a str,
+        mut is_allowed: IsAllowed,
+    ) -> DiagnosticContainer
+    where
+        IsAllowed: FnMut(&<Parser::Output as GetQuery>::Query) -> bool,
+        DiagnosticContainer:
+            FromIterator<Diagnostic<Parser::Error, <Parser::Output as GetQuery>::Query>>,
+    {
+        RecoveringParseIter::new(text, &self.parser)
+            .filter_map(|segment| match segment {
+                Err(error) => Some(Diagnostic::Parse(error)),
+                Ok(segment) => segment
+                    .query()
+                    .filter(|query| !is_allowed(query))
+                    .map(|query| Diagnostic::DisallowedQuery(query, segment.span())),
+            })
+            .collect()
+    }
+}
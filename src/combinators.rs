@@ -0,0 +1,97 @@
+use core::convert::Infallible;
+
+/// Extension methods to compose respond functions.
+///
+/// Every respond function (`FnMut(Query) -> Result<Output, Error>`) implements this trait, and every combinator
+/// returns another respond function, so the result can be passed to a [`Template`](crate::Template) as is:
+///
+/// ```
+/// # #[cfg(not(feature = "std"))] fn main() {}
+/// # #[cfg(feature = "std")] fn main() {
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{responders, simple_curly_braces, ResponderExt};
+/// use std::collections::BTreeMap;
+/// let system = simple_curly_braces();
+/// let local = BTreeMap::from([("name", "Alice")]);
+/// let secrets = BTreeMap::from([("token", "hunter2")]);
+/// let respond = responders::from_map(&local)
+///     .route_prefix("secret:", responders::from_map(&secrets))
+///     .map_output(|value| value.to_string())
+///     .or_else(responders::from_env())
+///     .with_default("(unknown)".to_string());
+/// let output = system
+///     .lazy_parse("{name} has {secret:token} and {LAZY_TEMPLATE_UNDEFINED_VARIABLE}")
+///     .to_string(respond)
+///     .unwrap();
+/// assert_eq!(output, "Alice has hunter2 and (unknown)");
+/// # }
+/// ```
+pub trait ResponderExt<Query, Output, Error>:
+    FnMut(Query) -> Result<Output, Error> + Sized
+{
+    /// Send the query to `next` if this respond function fails.
+    ///
+    /// The error of this respond function is discarded.
+    fn or_else<Next, NextError>(
+        mut self,
+        mut next: Next,
+    ) -> impl FnMut(Query) -> Result<Output, NextError>
+    where
+        Query: Clone,
+        Next: FnMut(Query) -> Result<Output, NextError>,
+    {
+        move |query| self(query.clone()).or_else(|_| next(query))
+    }
+
+    /// Send the queries that start with `prefix` to `route` (without the prefix), and the rest to this respond function.
+    fn route_prefix<'q, Prefix, Route>(
+        mut self,
+        prefix: Prefix,
+        mut route: Route,
+    ) -> impl FnMut(&'q str) -> Result<Output, Error>
+    where
+        Self: FnMut(&'q str) -> Result<Output, Error>,
+        Prefix: AsRef<str>,
+        Route: FnMut(&'q str) -> Result<Output, Error>,
+    {
+        move |query| match query.strip_prefix(prefix.as_ref()) {
+            Some(rest) => route(rest),
+            None => self(query),
+        }
+    }
+
+    /// Transform the successful responses.
+    fn map_output<Map, NewOutput>(
+        mut self,
+        mut map: Map,
+    ) -> impl FnMut(Query) -> Result<NewOutput, Error>
+    where
+        Map: FnMut(Output) -> NewOutput,
+    {
+        move |query| self(query).map(&mut map)
+    }
+
+    /// Transform the errors.
+    fn map_err<Map, NewError>(
+        mut self,
+        mut map: Map,
+    ) -> impl FnMut(Query) -> Result<Output, NewError>
+    where
+        Map: FnMut(Error) -> NewError,
+    {
+        move |query| self(query).map_err(&mut map)
+    }
+
+    /// Answer with `default` whenever this respond function fails.
+    fn with_default(mut self, default: Output) -> impl FnMut(Query) -> Result<Output, Infallible>
+    where
+        Output: Clone,
+    {
+        move |query| self(query).or_else(|_| Ok(default.clone()))
+    }
+}
+
+impl<Respond, Query, Output, Error> ResponderExt<Query, Output, Error> for Respond where
+    Respond: FnMut(Query) -> Result<Output, Error>
+{
+}
@@ -1,16 +1,52 @@
 pub mod simple_query;
+#[cfg(feature = "std")]
+pub use simple_query::OwnedSimpleQuery;
 pub use simple_query::{SimpleQuery, SimpleQueryParser};
 
 pub mod simple_escape;
 pub use simple_escape::{SimpleEscape, SimpleEscapeParser};
 
+pub mod nested_query;
+#[cfg(feature = "std")]
+pub use nested_query::OwnedNestedQuery;
+pub use nested_query::{NestedQuery, NestedQueryParser};
+
+pub mod default_query;
+#[cfg(feature = "std")]
+pub use default_query::OwnedDefaultQuery;
+pub use default_query::{DefaultQuery, DefaultQueryParser};
+
+pub mod formatted_query;
+#[cfg(feature = "std")]
+pub use formatted_query::OwnedFormattedQuery;
+pub use formatted_query::{FormatSpec, FormattedQuery, FormattedQueryParser};
+
+#[cfg(feature = "std")]
+pub mod filtered_query;
+#[cfg(feature = "std")]
+pub use filtered_query::{FilterRegistry, FilteredQuery, FilteredQueryParser, OwnedFilteredQuery};
+
+#[cfg(feature = "std")]
+pub mod block;
+#[cfg(feature = "std")]
+pub use block::{Block, BlockParser, Conditional, Loop};
+
+#[cfg(feature = "std")]
+pub mod partial;
+#[cfg(feature = "std")]
+pub use partial::{PartialParser, PartialSegment};
+
 pub mod simple_template_system;
 pub use simple_template_system::SimpleEnclosedTemplateSystem;
 
+mod comment;
 mod config;
+mod escaped;
 mod parser;
 mod segment;
 
+pub use comment::Comment;
 pub use config::*;
+pub use escaped::*;
 pub use parser::*;
 pub use segment::*;
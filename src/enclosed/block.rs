@@ -0,0 +1,765 @@
+use super::{comment, Comment, EnclosedTemplateParser, ParserConfig, Segment};
+use crate::{GetSpan, Parse, ParseInput, Recover, Render, RespondFields, Span};
+use core::{
+    fmt::{self, Write},
+    iter,
+};
+use derive_more::{Display, Error};
+use pipe_trait::Pipe;
+use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+/// Parse templates with conditional sections, such as `{#if discount}You save {discount}!{#else}No discount.{/if}`,
+/// and loop sections, such as `{#each items}{@index}. {.name}{#else}No items.{/each}`.
+///
+/// The condition of a conditional section is sent to the same responder as the other queries. The condition is
+/// considered true if its [response](SectionResponse) is truthy, and false otherwise (including when the responder
+/// fails). Only the queries of the taken branch are sent to the responder:
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{
+///     enclosed::{BlockParser, Parser, SimpleEscapeParser, SimpleQueryParser},
+///     IntoTemplateSystem,
+/// };
+/// let parser = Parser::curly_braces()
+///     .with_escape_parser(SimpleEscapeParser)
+///     .with_query_parser(SimpleQueryParser);
+/// let system = BlockParser::new(parser).into_template_system::<&str>();
+/// let template = system
+///     .eager_parse::<Vec<_>>("Hello, {name}!{#if discount} You save {discount}.{#else} {upsell}{/if}")
+///     .unwrap();
+/// let output = template
+///     .to_template()
+///     .to_string(|query| match query {
+///         "name" => Ok("Alice"),
+///         "discount" => Ok("10%"),
+///         _ => Err(format!("Can't answer {query:?}")),
+///     })
+///     .unwrap();
+/// assert_eq!(output, "Hello, Alice! You save 10%.");
+/// let output = template
+///     .to_template()
+///     .to_string(|query| match query {
+///         "name" => Ok("Bob"),
+///         "upsell" => Ok("Buy 2 get 1 free!"),
+///         _ => Err(format!("Can't answer {query:?}")),
+///     })
+///     .unwrap();
+/// assert_eq!(output, "Hello, Bob! Buy 2 get 1 free!");
+/// ```
+///
+/// The body of a loop section is rendered once per [item](SectionResponse::items) of the response to its list query.
+/// Inside the body, `{.key}` is answered by the current item (or by the items of the enclosing loops, from the
+/// innermost to the outermost), and `{@index}` by the zero-based index of the current item. The queries that the items
+/// cannot answer are sent to the responder. The `{#else}` branch is rendered when there are no items, including when
+/// the response is not a list or the responder fails:
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{
+///     enclosed::{block::Response, BlockParser, Parser, SimpleEscapeParser, SimpleQueryParser},
+///     IntoTemplateSystem,
+/// };
+/// use std::collections::BTreeMap;
+/// let parser = Parser::curly_braces()
+///     .with_escape_parser(SimpleEscapeParser)
+///     .with_query_parser(SimpleQueryParser);
+/// let system = BlockParser::new(parser).into_template_system::<&str>();
+/// let recipients = ["Alice", "Bob"].map(|name| BTreeMap::from([("name".to_string(), name)]));
+/// let output = system
+///     .lazy_parse("{#each recipients}{@index}: Dear {.name}, {greeting}\n{#else}Nobody{/each}")
+///     .to_string(|query| match query {
+///         "greeting" => Ok(Response::Value("hello!")),
+///         "recipients" => Ok(Response::List(recipients.to_vec())),
+///         _ => Err(format!("Can't answer {query:?}")),
+///     })
+///     .unwrap();
+/// assert_eq!(output, "0: Dear Alice, hello!\n1: Dear Bob, hello!\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlockParser<Inner> {
+    pub config: ParserConfig,
+    pub inner: Inner,
+}
+pub type Parser<Inner> = BlockParser<Inner>;
+
+impl<EscapeParser, QueryParser> Parser<EnclosedTemplateParser<EscapeParser, QueryParser>> {
+    /// Parse the sections with the delimiters of `inner`, and the other segments with `inner` itself.
+    pub fn new(inner: EnclosedTemplateParser<EscapeParser, QueryParser>) -> Self {
+        Parser {
+            config: inner.config.clone(),
+            inner,
+        }
+    }
+}
+
+pub type ParseOutput<'a, Query> = Block<'a, Query>;
+
+/// Segment of a template with sections.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Block<'a, Query> {
+    /// A segment outside of any section tag.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Segment(Segment<Query, &'a str>),
+    /// A conditional section.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    If(Conditional<'a, Query>),
+    /// A loop section.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    Each(Loop<'a, Query>),
+}
+
+/// A `{#if condition}...{#else}...{/if}` section.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Conditional<'a, Query> {
+    /// The query whose response decides which branch to render.
+    pub condition: &'a str,
+    /// Blocks to render when the condition is true.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub then: Vec<Block<'a, Query>>,
+    /// Blocks to render when the condition is false.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub otherwise: Vec<Block<'a, Query>>,
+    /// Span of the whole section, from the opening tag to the closing tag.
+    pub span: Span,
+}
+
+/// A `{#each list}...{#else}...{/each}` section.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Loop<'a, Query> {
+    /// The query whose response provides the items.
+    pub list: &'a str,
+    /// Blocks to render once per item.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub body: Vec<Block<'a, Query>>,
+    /// Blocks to render when there are no items.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub otherwise: Vec<Block<'a, Query>>,
+    /// Span of the whole section, from the opening tag to the closing tag.
+    pub span: Span,
+}
+
+impl<Query> GetSpan for Block<'_, Query> {
+    fn span(&self) -> Span {
+        match self {
+            Block::Segment(segment) => segment.span(),
+            Block::If(conditional) => conditional.span,
+            Block::Each(each) => each.span,
+        }
+    }
+}
+
+/// Error type of [`Parse`] on [`BlockParser`].
+#[derive(Debug, Display, Error, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseError<SegmentError> {
+    #[display("{_0}")]
+    Segment(SegmentError),
+    #[display("Unexpected tag {_0:?}")]
+    UnexpectedTag(Cow<'static, str>, Span),
+    #[display("Section is not closed")]
+    UnclosedSection(#[error(not(source))] Span),
+    #[display("Section has no condition")]
+    MissingCondition(#[error(not(source))] Span),
+    #[display("Section has no list")]
+    MissingList(#[error(not(source))] Span),
+}
+
+impl<SegmentError> GetSpan for ParseError<SegmentError>
+where
+    SegmentError: GetSpan,
+{
+    fn span(&self) -> Span {
+        match self {
+            ParseError::Segment(error) => error.span(),
+            ParseError::UnexpectedTag(_, span)
+            | ParseError::UnclosedSection(span)
+            | ParseError::MissingCondition(span)
+            | ParseError::MissingList(span) => *span,
+        }
+    }
+}
+
+type ParseBranchResult<'a, Output, SegmentError> =
+    Result<(Vec<Output>, (Tag<'a>, Span), &'a str), ParseError<SegmentError>>;
+
+/// Tags that delimit the sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tag<'a> {
+    If(&'a str),
+    Each(&'a str),
+    Else,
+    EndIf,
+    EndEach,
+}
+
+impl Tag<'_> {
+    fn name(self) -> &'static str {
+        match self {
+            Tag::If(_) => "#if",
+            Tag::Each(_) => "#each",
+            Tag::Else => "#else",
+            Tag::EndIf => "/if",
+            Tag::EndEach => "/each",
+        }
+    }
+}
+
+impl<'a, Inner, Query> Parse<'a> for Parser<Inner>
+where
+    Inner: Parse<'a, Output = Segment<Query, &'a str>>,
+{
+    type Output = ParseOutput<'a, Query>;
+    type Error = ParseError<Inner::Error>;
+
+    fn parse(&'a self, input: ParseInput<'a>) -> Result<(Self::Output, &'a str), Self::Error> {
+        let after_comments = self.skip_comments(input);
+        let Some((tag, rest)) = self.parse_tag(after_comments)? else {
+            // the inner parser skips the same comments
+            return self
+                .inner
+                .parse(input)
+                .map(|(segment, rest)| (Block::Segment(segment), rest))
+                .map_err(ParseError::Segment);
+        };
+        let input = after_comments;
+
+        let closing_tag = match tag {
+            Tag::If(_) => Tag::EndIf,
+            Tag::Each(_) => Tag::EndEach,
+            Tag::Else | Tag::EndIf | Tag::EndEach => {
+                return ParseError::UnexpectedTag(tag.name().into(), input.span_to(rest)).pipe(Err)
+            }
+        };
+
+        let opening_span = input.span_to(rest);
+        let (first, (end, end_span), rest) =
+            self.parse_branch(advance(input, rest), opening_span)?;
+        let (second, rest) = match end {
+            Tag::Else => match self.parse_branch(advance(input, rest), opening_span)? {
+                (second, (end, _), rest) if end == closing_tag => (second, rest),
+                (_, (end, end_span), _) => {
+                    return ParseError::UnexpectedTag(end.name().into(), end_span).pipe(Err)
+                }
+            },
+            _ if end == closing_tag => (Vec::new(), rest),
+            _ => return ParseError::UnexpectedTag(end.name().into(), end_span).pipe(Err),
+        };
+
+        let span = input.span_to(rest);
+        let block = match tag {
+            Tag::If(condition) => Block::If(Conditional {
+                condition,
+                then: first,
+                otherwise: second,
+                span,
+            }),
+            Tag::Each(list) => Block::Each(Loop {
+                list,
+                body: first,
+                otherwise: second,
+                span,
+            }),
+            Tag::Else | Tag::EndIf | Tag::EndEach => unreachable!("closing tags were rejected"),
+        };
+        Ok((block, rest))
+    }
+
+    fn skip_ignored(&'a self, input: ParseInput<'a>) -> &'a str {
+        self.skip_comments(input).text
+    }
+}
+
+impl<'a, Inner, Query> Recover<'a> for Parser<Inner>
+where
+    Inner: Recover<'a, Output = Segment<Query, &'a str>>,
+    Inner::Error: GetSpan,
+{
+    /// Let the inner parser skip a malformed segment outside of any section, skip the whole section that contains the
+    /// error, or skip the text covered by the error, such as a misplaced tag or the opening tag of an unclosed section.
+    fn recover(&self, input: ParseInput<'a>, error: &Self::Error) -> &'a str {
+        let after_comments = self.skip_comments(input);
+        let section = match (error, self.parse_tag(after_comments)) {
+            (ParseError::Segment(error), Ok(None)) => return self.inner.recover(input, error),
+            (_, Ok(Some((Tag::If(_) | Tag::Each(_), _))) | Err(_)) => {
+                self.skip_section(after_comments)
+            }
+            (_, Ok(Some(_) | None)) => None,
+        };
+        section.unwrap_or_else(|| skip_span(input, error.span()))
+    }
+}
+
+impl<'a, Inner> Parser<Inner>
+where
+    Inner: Parse<'a>,
+    Inner::Error: GetSpan,
+    Self: Parse<'a, Error = ParseError<Inner::Error>>,
+{
+    /// Skip the section that starts at `input` up to its closing tag, or return `None` if it is not closed.
+    ///
+    /// Only the tags are matched, so the section is skipped even if its content is malformed.
+    fn skip_section(&self, mut input: ParseInput<'a>) -> Option<&'a str> {
+        let open_bracket = self.config.open_bracket();
+        let mut depth = 0usize;
+        while let Some(index) = input.text.find(open_bracket) {
+            input = advance(input, &input.text[index..]);
+            let rest = match self.parse_tag(input) {
+                Ok(Some((Tag::If(_) | Tag::Each(_), rest))) => {
+                    depth += 1;
+                    rest
+                }
+                Ok(Some((Tag::EndIf | Tag::EndEach, rest))) => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return Some(rest);
+                    }
+                    rest
+                }
+                Ok(Some((Tag::Else, rest))) => rest,
+                // only opening tags fail to parse
+                Err(error) => {
+                    depth += 1;
+                    skip_span(input, error.span())
+                }
+                Ok(None) => match self.config.parse_comment(input) {
+                    Ok(Some((_, rest))) => rest,
+                    Ok(None) | Err(_) => &input.text[open_bracket.len()..],
+                },
+            };
+            input = advance(input, rest);
+        }
+        None
+    }
+}
+
+impl<'a, Inner> Parser<Inner>
+where
+    Inner: Parse<'a>,
+    Self: Parse<'a, Error = ParseError<Inner::Error>>,
+{
+    /// Parse a section tag at the start of `input`, if there is one.
+    fn parse_tag(
+        &self,
+        input: ParseInput<'a>,
+    ) -> Result<Option<(Tag<'a>, &'a str)>, ParseError<Inner::Error>> {
+        let (open_bracket, close_bracket) =
+            (self.config.open_bracket(), self.config.close_bracket());
+        let Some(tail) = input.text.strip_prefix(open_bracket) else {
+            return Ok(None);
+        };
+        let Some(close_index) = tail.find(close_bracket) else {
+            return Ok(None);
+        };
+        let body = tail[..close_index].trim();
+        let rest = &tail[(close_index + close_bracket.len())..];
+        let tag = match body {
+            "#else" => Tag::Else,
+            "/if" => Tag::EndIf,
+            "/each" => Tag::EndEach,
+            _ => match (strip_keyword(body, "#if"), strip_keyword(body, "#each")) {
+                (Some(""), _) => {
+                    return ParseError::MissingCondition(input.span_to(rest)).pipe(Err)
+                }
+                (_, Some("")) => return ParseError::MissingList(input.span_to(rest)).pipe(Err),
+                (Some(condition), _) => Tag::If(condition),
+                (_, Some(list)) => Tag::Each(list),
+                (None, None) => return Ok(None),
+            },
+        };
+        Ok(Some((tag, rest)))
+    }
+
+    /// Parse the blocks of a branch until an `{#else}`, `{/if}`, or `{/each}` tag.
+    ///
+    /// Return the blocks, the tag that ends the branch with its span, and the text after the tag.
+    fn parse_branch(
+        &'a self,
+        mut input: ParseInput<'a>,
+        opening_span: Span,
+    ) -> ParseBranchResult<'a, <Self as Parse<'a>>::Output, Inner::Error> {
+        let mut blocks = Vec::new();
+        loop {
+            input = self.skip_comments(input);
+            if input.text.is_empty() {
+                return ParseError::UnclosedSection(opening_span).pipe(Err);
+            }
+            if let Some((tag @ (Tag::Else | Tag::EndIf | Tag::EndEach), rest)) =
+                self.parse_tag(input)?
+            {
+                return Ok((blocks, (tag, input.span_to(rest)), rest));
+            }
+            let (block, rest) = self.parse(input)?;
+            blocks.push(block);
+            input = advance(input, rest);
+        }
+    }
+
+    /// Skip the comments at the start of `input`, but not the section tags that look like comments, such as `{#if x}`.
+    ///
+    /// Unclosed comments are left to the inner parser to report.
+    fn skip_comments(&self, mut input: ParseInput<'a>) -> ParseInput<'a> {
+        while let Ok(None) = self.parse_tag(input) {
+            let Ok(Some((_, rest))) = self.config.parse_comment(input) else {
+                break;
+            };
+            input = advance(input, rest);
+        }
+        input
+    }
+
+    /// List the comments of `text`, in order, up to the first parse error.
+    ///
+    /// _see more:_ [`EnclosedTemplateParser::comments`].
+    pub fn comments(&'a self, text: &'a str) -> impl Iterator<Item = Comment<'a>> + 'a {
+        comment::comments(text, |input| match self.parse_tag(input) {
+            Ok(Some((_, rest))) => Some((None, rest)),
+            Ok(None) => match self.config.parse_comment(input) {
+                Ok(Some((comment, rest))) => Some((Some(comment), rest)),
+                Ok(None) => self.inner.parse(input).ok().map(|(_, rest)| (None, rest)),
+                Err(_) => None,
+            },
+            Err(_) => None,
+        })
+    }
+}
+
+/// Get the trimmed text after `keyword` if `body` is `keyword` alone or `keyword` followed by whitespace.
+fn strip_keyword<'a>(body: &'a str, keyword: &str) -> Option<&'a str> {
+    let argument = body.strip_prefix(keyword)?;
+    (argument.is_empty() || argument.starts_with(char::is_whitespace)).then(|| argument.trim())
+}
+
+/// Get the text of `input` after `span`.
+fn skip_span<'a>(input: ParseInput<'a>, span: Span) -> &'a str {
+    let consumed = span.end.offset.saturating_sub(input.position.offset);
+    input.text.get(consumed..).unwrap_or_default()
+}
+
+/// Get the input that starts at `rest`, which is the remaining text of `input`.
+fn advance<'a>(input: ParseInput<'a>, rest: &'a str) -> ParseInput<'a> {
+    ParseInput {
+        text: rest,
+        position: input.span_to(rest).end,
+    }
+}
+
+/// Iterator over the items of a [`SectionResponse`].
+pub type Items<'r> = Box<dyn Iterator<Item = &'r dyn RespondFields> + 'r>;
+
+/// Response to the query of a section.
+///
+/// The default implementation treats the response as truthy if its display is neither empty nor `false`, and as having
+/// no items. Responses that fail to be displayed are falsy.
+pub trait SectionResponse: fmt::Display {
+    /// Whether the response takes the `then` branch of a [conditional section](Conditional).
+    fn is_truthy(&self) -> bool {
+        let mut truthiness = Truthiness {
+            len: 0,
+            is_false_prefix: true,
+        };
+        write!(truthiness, "{self}").is_ok() && truthiness.is_truthy()
+    }
+
+    /// The items to render the body of a [loop section](Loop) with, or `None` if the response is not a list.
+    fn items(&self) -> Option<Items<'_>> {
+        None
+    }
+}
+
+/// Writer that checks whether the displayed text is neither empty nor `false`, without allocation.
+struct Truthiness {
+    len: usize,
+    is_false_prefix: bool,
+}
+
+impl Truthiness {
+    fn is_truthy(&self) -> bool {
+        let is_false = self.is_false_prefix && self.len == "false".len();
+        self.len != 0 && !is_false
+    }
+}
+
+impl Write for Truthiness {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let start = self.len;
+        self.len += text.len();
+        self.is_false_prefix = self.is_false_prefix && "false".get(start..self.len) == Some(text);
+        Ok(())
+    }
+}
+
+macro_rules! impl_section_response {
+    ($($type:ty),* $(,)?) => {$(
+        impl SectionResponse for $type {}
+    )*};
+}
+
+impl_section_response! {
+    str, String, Box<str>, Rc<str>, Arc<str>, char, bool, dyn fmt::Display,
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64,
+}
+
+impl SectionResponse for Cow<'_, str> {}
+
+impl<Response> SectionResponse for &Response
+where
+    Response: SectionResponse + ?Sized,
+{
+    fn is_truthy(&self) -> bool {
+        Response::is_truthy(self)
+    }
+
+    fn items(&self) -> Option<Items<'_>> {
+        Response::items(self)
+    }
+}
+
+/// Response that is either a single value or a list of items, for responders that answer both kinds of queries.
+///
+/// A list displays as an empty string, and is truthy if it is not empty.
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+pub enum Response<Value, Item> {
+    #[display("{_0}")]
+    Value(Value),
+    #[display("")]
+    List(Vec<Item>),
+}
+
+impl<Value, Item> SectionResponse for Response<Value, Item>
+where
+    Value: SectionResponse,
+    Item: RespondFields,
+{
+    fn is_truthy(&self) -> bool {
+        match self {
+            Response::Value(value) => value.is_truthy(),
+            Response::List(items) => !items.is_empty(),
+        }
+    }
+
+    fn items(&self) -> Option<Items<'_>> {
+        match self {
+            Response::Value(value) => value.items(),
+            Response::List(items) => Some(Box::new(
+                items.iter().map(|item| item as &dyn RespondFields),
+            )),
+        }
+    }
+}
+
+/// Response to the queries inside a section, which may be answered by the items of the enclosing loops.
+#[derive(Debug, Display, Clone)]
+pub enum ScopedResponse<Response> {
+    /// Answer of the responder.
+    #[display("{_0}")]
+    Outer(Response),
+    /// Answer of a `{.key}` query by an item.
+    #[display("{_0}")]
+    Item(String),
+    /// Answer of an `{@index}` query.
+    #[display("{_0}")]
+    Index(usize),
+}
+
+impl<Response> SectionResponse for ScopedResponse<Response>
+where
+    Response: SectionResponse,
+{
+    fn is_truthy(&self) -> bool {
+        match self {
+            ScopedResponse::Outer(response) => response.is_truthy(),
+            ScopedResponse::Item(value) => value.is_truthy(),
+            ScopedResponse::Index(index) => index.is_truthy(),
+        }
+    }
+
+    fn items(&self) -> Option<Items<'_>> {
+        match self {
+            ScopedResponse::Outer(response) => response.items(),
+            ScopedResponse::Item(_) | ScopedResponse::Index(_) => None,
+        }
+    }
+}
+
+/// Respond function that the segments of a [`Block`] are rendered with.
+pub type ScopedRespond<'r, 'a, Response, Error> =
+    dyn FnMut(&'a str) -> Result<ScopedResponse<Response>, Error> + 'r;
+
+/// Current item of a loop, linked to the current items of the enclosing loops.
+struct Scope<'s> {
+    item: &'s dyn RespondFields,
+    index: usize,
+    parent: Option<&'s Scope<'s>>,
+}
+
+/// Answer `query` with the items of `scope` if it is an item-level query, or with `respond` otherwise.
+///
+/// An item that fails to be displayed answers with an empty text, and its error is stored in `write_error` for the
+/// caller to report.
+fn respond_in_scope<'a, Respond, Response, Error>(
+    respond: &mut Respond,
+    scope: Option<&Scope<'_>>,
+    write_error: &mut Option<fmt::Error>,
+    query: &'a str,
+) -> Result<ScopedResponse<Response>, Error>
+where
+    Respond: FnMut(&'a str) -> Result<Response, Error>,
+{
+    if let Some(scope) = scope {
+        if query == "@index" {
+            return Ok(ScopedResponse::Index(scope.index));
+        }
+        if let Some(key) = query.strip_prefix('.') {
+            let value = iter::successors(Some(scope), |scope| scope.parent)
+                .find_map(|scope| scope.item.respond(key).ok());
+            if let Some(value) = value {
+                let mut text = String::new();
+                if let Err(error) = write!(text, "{value}") {
+                    text.clear();
+                    write_error.get_or_insert(error);
+                }
+                return Ok(ScopedResponse::Item(text));
+            }
+        }
+    }
+    respond(query).map(ScopedResponse::Outer)
+}
+
+/// Returned upon the [rendering](Render) of a [`Block`].
+#[derive(Debug, Display, Clone)]
+pub enum BlockOutput<SegmentOutput> {
+    #[display("{_0}")]
+    Segment(SegmentOutput),
+    #[display("{_0}")]
+    Section(String),
+}
+
+/// Error returned by the [rendering](Render) of a [`Block`].
+#[derive(Debug, Display, Error, Clone, Copy)]
+pub enum RenderError<SegmentError> {
+    /// A segment failed to be rendered.
+    #[display("{_0}")]
+    Segment(SegmentError),
+    /// A segment inside a section, or an item that answers one of its queries, failed to be displayed.
+    #[display("{_0}")]
+    Write(fmt::Error),
+}
+
+impl<'a, Query, Respond, SegmentOutput, SegmentError, QueryOutput, Error>
+    Render<Respond, BlockOutput<SegmentOutput>, RenderError<SegmentError>> for Block<'a, Query>
+where
+    Query: Clone,
+    Segment<Query, &'a str>: for<'r> Render<
+        &'r mut ScopedRespond<'r, 'a, QueryOutput, Error>,
+        SegmentOutput,
+        SegmentError,
+    >,
+    SegmentOutput: fmt::Display,
+    Respond: FnMut(&'a str) -> Result<QueryOutput, Error>,
+    QueryOutput: SectionResponse,
+{
+    fn render(
+        self,
+        respond: &mut Respond,
+    ) -> Result<BlockOutput<SegmentOutput>, RenderError<SegmentError>> {
+        if let Block::Segment(segment) = self {
+            return render_segment(segment, respond, None).map(BlockOutput::Segment);
+        }
+        let mut output = String::new();
+        self.render_in_scope(respond, None, &mut output)?;
+        Ok(BlockOutput::Section(output))
+    }
+}
+
+impl<'a, Query> Block<'a, Query> {
+    /// Render the block with the items of `scope` and `respond`, and write the result to `output`.
+    fn render_in_scope<Output, Respond, SegmentOutput, SegmentError, QueryOutput, Error>(
+        self,
+        respond: &mut Respond,
+        scope: Option<&Scope<'_>>,
+        output: &mut Output,
+    ) -> Result<(), RenderError<SegmentError>>
+    where
+        Output: Write,
+        Query: Clone,
+        Segment<Query, &'a str>: for<'r> Render<
+            &'r mut ScopedRespond<'r, 'a, QueryOutput, Error>,
+            SegmentOutput,
+            SegmentError,
+        >,
+        SegmentOutput: fmt::Display,
+        Respond: FnMut(&'a str) -> Result<QueryOutput, Error>,
+        QueryOutput: SectionResponse,
+    {
+        match self {
+            Block::Segment(segment) => {
+                let segment_output = render_segment(segment, respond, scope)?;
+                write!(output, "{segment_output}").map_err(RenderError::Write)?;
+            }
+            Block::If(conditional) => {
+                // Like other responses, an item that fails to be displayed is falsy.
+                let condition = respond_in_scope(respond, scope, &mut None, conditional.condition);
+                let branch = match condition {
+                    Ok(response) if response.is_truthy() => conditional.then,
+                    Ok(_) | Err(_) => conditional.otherwise,
+                };
+                for block in branch {
+                    block.render_in_scope(respond, scope, output)?;
+                }
+            }
+            Block::Each(each) => {
+                let response = respond_in_scope(respond, scope, &mut None, each.list).ok();
+                let items = response.as_ref().and_then(SectionResponse::items);
+                let mut is_empty = true;
+                for (index, item) in items.into_iter().flatten().enumerate() {
+                    is_empty = false;
+                    let scope = Scope {
+                        item,
+                        index,
+                        parent: scope,
+                    };
+                    for block in each.body.iter().cloned() {
+                        block.render_in_scope(respond, Some(&scope), output)?;
+                    }
+                }
+                if is_empty {
+                    for block in each.otherwise {
+                        block.render_in_scope(respond, scope, output)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Render a segment with the items of `scope` and `respond`.
+fn render_segment<'a, Query, Respond, SegmentOutput, SegmentError, QueryOutput, Error>(
+    segment: Segment<Query, &'a str>,
+    respond: &mut Respond,
+    scope: Option<&Scope<'_>>,
+) -> Result<SegmentOutput, RenderError<SegmentError>>
+where
+    Segment<Query, &'a str>: for<'r> Render<
+        &'r mut ScopedRespond<'r, 'a, QueryOutput, Error>,
+        SegmentOutput,
+        SegmentError,
+    >,
+    Respond: FnMut(&'a str) -> Result<QueryOutput, Error>,
+{
+    let mut write_error = None;
+    let segment_output = {
+        let mut respond =
+            |query: &'a str| respond_in_scope(respond, scope, &mut write_error, query);
+        let mut respond: &mut ScopedRespond<'_, 'a, QueryOutput, Error> = &mut respond;
+        segment.render(&mut respond)
+    };
+    match write_error {
+        Some(error) => Err(RenderError::Write(error)),
+        None => segment_output.map_err(RenderError::Segment),
+    }
+}
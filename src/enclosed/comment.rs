@@ -0,0 +1,48 @@
+use crate::{GetSpan, ParseInput, Span};
+use core::iter;
+
+/// A comment in a template, such as `{# note #}`.
+///
+/// Comments are only recognized when [`ParserConfig::comment`](super::ParserConfig::comment) is set. They produce no
+/// segment, but they can be listed for tooling by
+/// [`EnclosedTemplateParser::comments`](super::EnclosedTemplateParser::comments).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comment<'a> {
+    /// The text between the delimiters, without trimming.
+    pub text: &'a str,
+    /// Span of the whole comment, including the delimiters.
+    pub span: Span,
+}
+
+impl GetSpan for Comment<'_> {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Iterate over the comments of `text`.
+///
+/// `step` parses a comment or any other component at the start of its input, and returns it along with the remaining
+/// text. The iteration stops when `step` returns `None`.
+pub(crate) fn comments<'a, Step>(text: &'a str, mut step: Step) -> impl Iterator<Item = Comment<'a>>
+where
+    Step: FnMut(ParseInput<'a>) -> Option<(Option<Comment<'a>>, &'a str)>,
+{
+    let mut input = ParseInput::new(text);
+    iter::from_fn(move || {
+        while !input.text.is_empty() {
+            let (comment, rest) = step(input)?;
+            if rest.len() >= input.text.len() {
+                return None;
+            }
+            input = ParseInput {
+                text: rest,
+                position: input.span_to(rest).end,
+            };
+            if comment.is_some() {
+                return comment;
+            }
+        }
+        None
+    })
+}
@@ -1,20 +1,296 @@
-#[derive(Debug, Clone, Copy)]
+use super::Comment;
+use crate::{ParseInput, Position, Span};
+use alloc::borrow::Cow;
+use derive_more::{Display, Error};
+use pipe_trait::Pipe;
+
+/// Delimiters of the queries of an [`EnclosedTemplateParser`](super::EnclosedTemplateParser).
+///
+/// The delimiters are either static strings, such as those of the presets, or owned strings chosen at runtime:
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::enclosed::{ConfigError, ParserConfig};
+/// let open_bracket = String::from("<%");
+/// let config = ParserConfig::new(open_bracket, "%>").unwrap();
+/// assert_eq!((config.open_bracket(), config.close_bracket()), ("<%", "%>"));
+/// assert_eq!(ParserConfig::new("", "}").unwrap_err(), ConfigError::EmptyOpenBracket);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedParserConfig")
+)]
 pub struct ParserConfig {
-    pub open_bracket: char,
-    pub close_bracket: char,
+    pub(crate) open_bracket: Delimiter,
+    pub(crate) close_bracket: Delimiter,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub(crate) comment: Option<CommentConfig>,
+}
+
+/// Delimiter of a [`ParserConfig`] or a [`CommentConfig`].
+pub type Delimiter = Cow<'static, str>;
+
+/// Error returned by [`ParserConfig::new`] and [`CommentConfig::new`].
+#[derive(Debug, Display, Error, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    #[display("Opening delimiter must not be empty")]
+    EmptyOpenBracket,
+    #[display("Closing delimiter must not be empty")]
+    EmptyCloseBracket,
+}
+
+/// Check that neither delimiter is empty.
+fn check_delimiters(open_bracket: &str, close_bracket: &str) -> Result<(), ConfigError> {
+    if open_bracket.is_empty() {
+        return Err(ConfigError::EmptyOpenBracket);
+    }
+    if close_bracket.is_empty() {
+        return Err(ConfigError::EmptyCloseBracket);
+    }
+    Ok(())
 }
 
 impl ParserConfig {
-    pub fn curly_braces() -> Self {
+    /// Create a config whose queries are placed between `open_bracket` and `close_bracket`.
+    ///
+    /// Both delimiters must not be empty.
+    pub fn new(
+        open_bracket: impl Into<Delimiter>,
+        close_bracket: impl Into<Delimiter>,
+    ) -> Result<Self, ConfigError> {
+        let open_bracket = open_bracket.into();
+        let close_bracket = close_bracket.into();
+        check_delimiters(&open_bracket, &close_bracket)?;
+        Ok(ParserConfig {
+            open_bracket,
+            close_bracket,
+            comment: None,
+        })
+    }
+
+    /// Create a config from delimiters that are known not to be empty.
+    const fn from_static(open_bracket: &'static str, close_bracket: &'static str) -> Self {
         ParserConfig {
-            open_bracket: '{',
-            close_bracket: '}',
+            open_bracket: Cow::Borrowed(open_bracket),
+            close_bracket: Cow::Borrowed(close_bracket),
+            comment: None,
+        }
+    }
+
+    /// Queries are placed between `{` and `}`, such as `{name}`.
+    pub fn curly_braces() -> Self {
+        ParserConfig::from_static("{", "}")
+    }
+
+    /// Queries are placed between `{{` and `}}`, such as `{{name}}`.
+    pub fn double_curly_braces() -> Self {
+        ParserConfig::from_static("{{", "}}")
+    }
+
+    /// Queries are placed between `${` and `}`, such as `${name}`.
+    pub fn dollar_curly_braces() -> Self {
+        ParserConfig::from_static("${", "}")
+    }
+
+    /// Opening delimiter of the queries.
+    pub fn open_bracket(&self) -> &str {
+        &self.open_bracket
+    }
+
+    /// Closing delimiter of the queries.
+    pub fn close_bracket(&self) -> &str {
+        &self.close_bracket
+    }
+
+    /// Delimiters of the comments, or `None` if comments are not recognized.
+    pub fn comment(&self) -> Option<&CommentConfig> {
+        self.comment.as_ref()
+    }
+
+    /// Recognize comments between the delimiters of `comment`.
+    pub fn with_comment(mut self, comment: CommentConfig) -> Self {
+        self.comment = Some(comment);
+        self
+    }
+
+    /// Parse a comment at the start of `input`, if comments are recognized and there is one.
+    ///
+    /// Return the comment and the remaining text, or the empty span at the end of `input` if the comment is not closed.
+    pub fn parse_comment<'a>(
+        &self,
+        input: ParseInput<'a>,
+    ) -> Result<Option<(Comment<'a>, &'a str)>, Span> {
+        let Some(comment) = &self.comment else {
+            return Ok(None);
+        };
+        let (open_bracket, close_bracket) = (comment.open_bracket(), comment.close_bracket());
+        let Some(tail) = input.text.strip_prefix(open_bracket) else {
+            return Ok(None);
+        };
+        let Some(close_index) = tail.find(close_bracket) else {
+            return Span::empty(input.span_to("").end).pipe(Err);
+        };
+        let rest = &tail[(close_index + close_bracket.len())..];
+        let comment = Comment {
+            text: &tail[..close_index],
+            span: input.span_to(rest),
+        };
+        Ok(Some((comment, rest)))
+    }
+}
+
+/// Delimiters of the comments of an [`EnclosedTemplateParser`](super::EnclosedTemplateParser).
+///
+/// Comments are removed at parse time: they produce no segment, so they never reach the responder. Both delimiters must
+/// not be empty.
+///
+/// ```
+/// # #[cfg(not(feature = "std"))] fn main() {}
+/// # #[cfg(feature = "std")] fn main() {
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{
+///     enclosed::{CommentConfig, Parser, ParserConfig, SimpleEscapeParser, SimpleQuery, SimpleQueryParser},
+///     IntoTemplateSystem,
+/// };
+/// let config = ParserConfig::curly_braces().with_comment(CommentConfig::hash_curly_braces());
+/// let output = Parser::curly_braces()
+///     .with_config(config)
+///     .with_escape_parser(SimpleEscapeParser)
+///     .with_query_parser(SimpleQueryParser)
+///     .into_template_system::<SimpleQuery>()
+///     .lazy_parse("Hello, {name}!{# TODO: ask for {age} #}")
+///     .to_string(|query| match query {
+///         "name" => Ok("Alice"),
+///         _ => Err(format!("Can't answer {query:?}")),
+///     })
+///     .unwrap();
+/// assert_eq!(output, "Hello, Alice!");
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedCommentConfig")
+)]
+pub struct CommentConfig {
+    open_bracket: Delimiter,
+    close_bracket: Delimiter,
+}
+
+impl CommentConfig {
+    /// Create a config whose comments are placed between `open_bracket` and `close_bracket`.
+    ///
+    /// Both delimiters must not be empty.
+    pub fn new(
+        open_bracket: impl Into<Delimiter>,
+        close_bracket: impl Into<Delimiter>,
+    ) -> Result<Self, ConfigError> {
+        let open_bracket = open_bracket.into();
+        let close_bracket = close_bracket.into();
+        check_delimiters(&open_bracket, &close_bracket)?;
+        Ok(CommentConfig {
+            open_bracket,
+            close_bracket,
+        })
+    }
+
+    /// Comments are placed between `{#` and `#}`, such as `{# note #}`.
+    pub fn hash_curly_braces() -> Self {
+        CommentConfig {
+            open_bracket: Cow::Borrowed("{#"),
+            close_bracket: Cow::Borrowed("#}"),
         }
     }
+
+    /// Opening delimiter of the comments.
+    pub fn open_bracket(&self) -> &str {
+        &self.open_bracket
+    }
+
+    /// Closing delimiter of the comments.
+    pub fn close_bracket(&self) -> &str {
+        &self.close_bracket
+    }
+}
+
+/// Deserialized fields of a [`ParserConfig`] before they are checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedParserConfig {
+    open_bracket: Delimiter,
+    close_bracket: Delimiter,
+    #[serde(default)]
+    comment: Option<CommentConfig>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedParserConfig> for ParserConfig {
+    type Error = ConfigError;
+    fn try_from(unchecked: UncheckedParserConfig) -> Result<Self, Self::Error> {
+        let UncheckedParserConfig {
+            open_bracket,
+            close_bracket,
+            comment,
+        } = unchecked;
+        let config = ParserConfig::new(open_bracket, close_bracket)?;
+        Ok(ParserConfig { comment, ..config })
+    }
+}
+
+/// Deserialized fields of a [`CommentConfig`] before they are checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedCommentConfig {
+    open_bracket: Delimiter,
+    close_bracket: Delimiter,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedCommentConfig> for CommentConfig {
+    type Error = ConfigError;
+    fn try_from(unchecked: UncheckedCommentConfig) -> Result<Self, Self::Error> {
+        CommentConfig::new(unchecked.open_bracket, unchecked.close_bracket)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct ComponentParserInput<'a> {
     pub text: &'a str,
-    pub config: ParserConfig,
+    pub position: Position,
+    pub config: &'a ParserConfig,
+}
+
+impl<'a> ComponentParserInput<'a> {
+    /// Get the span of the text consumed by a component parser which returned `rest` as the remaining text.
+    pub fn span_to(&self, rest: &str) -> Span {
+        Span::covering(self.position, &self.text[..(self.text.len() - rest.len())])
+    }
+
+    /// Strip the opening bracket from the start of [`text`](ComponentParserInput::text), and return the remaining text.
+    ///
+    /// Otherwise, fail with `None` so that the query parser is skipped, or with the error built by `unexpected_token` if
+    /// the text starts with a closing bracket instead.
+    pub fn strip_open_bracket<Error>(
+        &self,
+        unexpected_token: impl FnOnce(Delimiter, Span) -> Error,
+    ) -> Result<&'a str, Option<Error>> {
+        if let Some(tail) = self.text.strip_prefix(self.config.open_bracket()) {
+            return Ok(tail);
+        }
+        match self.text.strip_prefix(self.config.close_bracket()) {
+            Some(tail) => unexpected_token(self.config.close_bracket.clone(), self.span_to(tail))
+                .pipe(Some)
+                .pipe(Err),
+            None => Err(None),
+        }
+    }
+
+    /// Get the span of `part`, which must be a slice of [`text`](ComponentParserInput::text).
+    pub fn span_of(&self, part: &str) -> Span {
+        let offset = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        Span::covering(self.position.advance(&self.text[..offset]), part)
+    }
 }
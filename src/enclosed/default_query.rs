@@ -0,0 +1,260 @@
+use super::{ComponentParserInput, ParserConfig};
+use crate::{AsyncRender, GetSpan, Parse, Render, Span};
+use alloc::borrow::Cow;
+use core::future::Future;
+use derive_more::{Display, Error};
+
+#[cfg(feature = "std")]
+use super::OwnedSimpleQuery;
+#[cfg(feature = "std")]
+use crate::IntoOwned;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+pub type ParserInput<'a> = ComponentParserInput<'a>;
+
+/// Parse queries with an optional default value, such as `{name ?? "anonymous"}` or `{name:-anonymous}`.
+///
+/// If the responder fails to answer a query that has a default value, the default value is rendered instead:
+///
+/// ```
+/// # #[cfg(not(feature = "std"))] fn main() {}
+/// # #[cfg(feature = "std")] fn main() {
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{
+///     enclosed::{DefaultQueryParser, Parser, SimpleEscapeParser},
+///     IntoTemplateSystem,
+/// };
+/// let output = Parser::curly_braces()
+///     .with_escape_parser(SimpleEscapeParser)
+///     .with_query_parser(DefaultQueryParser)
+///     .into_template_system::<&str>()
+///     .lazy_parse(r#"Hello, {name ?? "anonymous"} from {country:-nowhere}!"#)
+///     .to_string(|query| match query {
+///         "country" => Ok("Vietnam"),
+///         _ => Err(format!("Can't answer {query:?}")),
+///     })
+///     .unwrap();
+/// assert_eq!(output, "Hello, anonymous from Vietnam!");
+/// # }
+/// ```
+///
+/// Whitespace around the query and around an unquoted default value is ignored, use a string literal to keep it.
+///
+/// Responders may signal that a query has no answer by returning an error. Errors of queries without default values
+/// still fail the template.
+#[derive(Debug, Clone, Copy)]
+pub struct DefaultQueryParser;
+pub type Parser = DefaultQueryParser;
+
+pub type ParseOutput<'a> = DefaultQuery<'a>;
+
+/// A query with an optional default value.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DefaultQuery<'a> {
+    pub query: &'a str,
+    pub default: Option<&'a str>,
+}
+
+#[derive(Debug, Display, Error, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseError {
+    #[display("Unexpected token {_0:?}")]
+    UnexpectedToken(Cow<'static, str>, Span),
+    #[display("Unexpected end of input")]
+    UnexpectedEndOfInput(#[error(not(source))] Span),
+    #[display("Default value should be a string literal")]
+    InvalidDefault(#[error(not(source))] Span),
+}
+
+impl GetSpan for ParseError {
+    fn span(&self) -> Span {
+        match self {
+            ParseError::UnexpectedToken(_, span)
+            | ParseError::UnexpectedEndOfInput(span)
+            | ParseError::InvalidDefault(span) => *span,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl IntoOwned for ParseError {
+    type Owned = Self;
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
+}
+
+impl<'a> Parse<'a, ParserInput<'a>> for Parser {
+    type Output = ParseOutput<'a>;
+    type Error = Option<ParseError>;
+
+    fn parse(&self, input: ParserInput<'a>) -> Result<(Self::Output, &'a str), Self::Error> {
+        let close_bracket = input.config.close_bracket();
+
+        let tail = input.strip_open_bracket(ParseError::UnexpectedToken)?;
+
+        let end_of_input = || ParseError::UnexpectedEndOfInput(Span::empty(input.span_to("").end));
+        let close_index = tail
+            .find(close_bracket)
+            .ok_or_else(end_of_input)
+            .map_err(Some)?;
+
+        let coalesce_index = tail[..close_index].find("??");
+        if let Some(coalesce_index) = coalesce_index {
+            let query = tail[..coalesce_index].trim();
+            let (default, rest) = parse_string_literal(input, &tail[(coalesce_index + 2)..])?;
+            let rest = rest
+                .trim_start()
+                .strip_prefix(close_bracket)
+                .ok_or_else(|| match until_close_bracket(rest, input.config) {
+                    "" => end_of_input(),
+                    unexpected => ParseError::InvalidDefault(input.span_of(unexpected)),
+                })
+                .map_err(Some)?;
+            let default = Some(default);
+            return Ok((DefaultQuery { query, default }, rest));
+        }
+
+        let body = &tail[..close_index];
+        let rest = &tail[(close_index + close_bracket.len())..];
+        let query = match body.split_once(":-") {
+            Some((query, default)) => DefaultQuery {
+                query: query.trim(),
+                default: Some(default.trim()),
+            },
+            None => DefaultQuery {
+                query: body,
+                default: None,
+            },
+        };
+        Ok((query, rest))
+    }
+}
+
+/// Parse a string literal such as `"anonymous"`.
+fn parse_string_literal<'a>(
+    input: ParserInput<'a>,
+    text: &'a str,
+) -> Result<(&'a str, &'a str), Option<ParseError>> {
+    let text = text.trim_start();
+    let invalid_default =
+        || ParseError::InvalidDefault(input.span_of(until_close_bracket(text, input.config)));
+    let tail = text
+        .strip_prefix('"')
+        .ok_or_else(invalid_default)
+        .map_err(Some)?;
+    let quote_index = tail
+        .find('"')
+        .ok_or_else(|| ParseError::UnexpectedEndOfInput(Span::empty(input.span_to("").end)))
+        .map_err(Some)?;
+    Ok((&tail[..quote_index], &tail[(quote_index + 1)..]))
+}
+
+/// Get the trimmed text before the closing bracket (or the end of input).
+fn until_close_bracket<'a>(text: &'a str, config: &ParserConfig) -> &'a str {
+    let end = text.find(config.close_bracket()).unwrap_or(text.len());
+    text[..end].trim()
+}
+
+/// Returned upon the [rendering](Render) of a [`DefaultQuery`].
+#[derive(Debug, Display, Clone, Copy)]
+pub enum DefaultQueryOutput<'a, Output> {
+    Response(Output),
+    Default(&'a str),
+}
+
+impl<'a, Respond, Output, Error> Render<Respond, DefaultQueryOutput<'a, Output>, Error>
+    for DefaultQuery<'a>
+where
+    Respond: FnMut(&'a str) -> Result<Output, Error>,
+{
+    fn render(self, respond: &mut Respond) -> Result<DefaultQueryOutput<'a, Output>, Error> {
+        match (respond(self.query), self.default) {
+            (Ok(output), _) => Ok(DefaultQueryOutput::Response(output)),
+            (Err(_), Some(default)) => Ok(DefaultQueryOutput::Default(default)),
+            (Err(error), None) => Err(error),
+        }
+    }
+}
+
+impl<'a, Respond, QueryFuture, Output, Error>
+    AsyncRender<Respond, DefaultQueryOutput<'a, Output>, Error> for DefaultQuery<'a>
+where
+    Respond: FnMut(&'a str) -> QueryFuture,
+    QueryFuture: Future<Output = Result<Output, Error>>,
+{
+    async fn render_async(
+        self,
+        respond: &mut Respond,
+    ) -> Result<DefaultQueryOutput<'a, Output>, Error> {
+        match (respond(self.query).await, self.default) {
+            (Ok(output), _) => Ok(DefaultQueryOutput::Response(output)),
+            (Err(_), Some(default)) => Ok(DefaultQueryOutput::Default(default)),
+            (Err(error), None) => Err(error),
+        }
+    }
+}
+
+/// Owned counterpart of [`DefaultQuery`], see [`IntoOwned`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedDefaultQuery {
+    pub query: OwnedSimpleQuery,
+    pub default: Option<Arc<str>>,
+}
+
+#[cfg(feature = "std")]
+impl IntoOwned for DefaultQuery<'_> {
+    type Owned = OwnedDefaultQuery;
+    fn into_owned(self) -> Self::Owned {
+        OwnedDefaultQuery {
+            query: self.query.into_owned(),
+            default: self.default.map(IntoOwned::into_owned),
+        }
+    }
+}
+
+/// Returned upon the [rendering](Render) of an [`OwnedDefaultQuery`].
+#[cfg(feature = "std")]
+#[derive(Debug, Display, Clone)]
+pub enum OwnedDefaultQueryOutput<Output> {
+    Response(Output),
+    Default(Arc<str>),
+}
+
+#[cfg(feature = "std")]
+impl<Respond, Output, Error> Render<Respond, OwnedDefaultQueryOutput<Output>, Error>
+    for OwnedDefaultQuery
+where
+    Respond: FnMut(OwnedSimpleQuery) -> Result<Output, Error>,
+{
+    fn render(self, respond: &mut Respond) -> Result<OwnedDefaultQueryOutput<Output>, Error> {
+        match (respond(self.query), self.default) {
+            (Ok(output), _) => Ok(OwnedDefaultQueryOutput::Response(output)),
+            (Err(_), Some(default)) => Ok(OwnedDefaultQueryOutput::Default(default)),
+            (Err(error), None) => Err(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Respond, QueryFuture, Output, Error>
+    AsyncRender<Respond, OwnedDefaultQueryOutput<Output>, Error> for OwnedDefaultQuery
+where
+    Respond: FnMut(OwnedSimpleQuery) -> QueryFuture,
+    QueryFuture: Future<Output = Result<Output, Error>>,
+{
+    async fn render_async(
+        self,
+        respond: &mut Respond,
+    ) -> Result<OwnedDefaultQueryOutput<Output>, Error> {
+        match (respond(self.query).await, self.default) {
+            (Ok(output), _) => Ok(OwnedDefaultQueryOutput::Response(output)),
+            (Err(_), Some(default)) => Ok(OwnedDefaultQueryOutput::Default(default)),
+            (Err(error), None) => Err(error),
+        }
+    }
+}
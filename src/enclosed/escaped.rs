@@ -0,0 +1,21 @@
+/// Output of an escape parser of an [`EnclosedTemplateParser`](super::EnclosedTemplateParser).
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Escaped<'a> {
+    /// A single character, such as one made from `\n`.
+    Character(char),
+    /// A string, such as an escaped delimiter.
+    Text(&'a str),
+}
+
+impl From<char> for Escaped<'_> {
+    fn from(value: char) -> Self {
+        Escaped::Character(value)
+    }
+}
+
+impl<'a> From<&'a str> for Escaped<'a> {
+    fn from(value: &'a str) -> Self {
+        Escaped::Text(value)
+    }
+}
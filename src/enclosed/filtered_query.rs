@@ -0,0 +1,366 @@
+use super::ComponentParserInput;
+use super::OwnedSimpleQuery;
+use crate::{AsyncRender, GetSpan, IntoOwned, Parse, Render, Span};
+use alloc::borrow::Cow;
+use core::{fmt, future::Future};
+use derive_more::{Display, Error};
+use std::sync::Arc;
+
+mod args;
+mod registry;
+
+pub use args::*;
+pub use registry::*;
+
+pub type ParserInput<'a> = ComponentParserInput<'a>;
+
+/// Parse queries followed by a chain of filters, such as `{name | trim | truncate(10)}`.
+///
+/// The response of the query is passed through the filters from left to right. The filters are looked up in the
+/// [`FilterRegistry`] when the template is parsed, so unknown filters and invalid arguments are reported as parse errors:
+///
+/// ```
+/// # #[cfg(not(feature = "std"))] fn main() {}
+/// # #[cfg(feature = "std")] fn main() {
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{
+///     enclosed::{FilterRegistry, FilteredQueryParser, Parser, SimpleEscapeParser},
+///     IntoTemplateSystem,
+/// };
+/// let registry = FilterRegistry::builtin();
+/// let system = Parser::curly_braces()
+///     .with_escape_parser(SimpleEscapeParser)
+///     .with_query_parser(FilteredQueryParser::new(&registry))
+///     .into_template_system::<&str>();
+/// let output = system
+///     .lazy_parse("{name | upper} ({title | truncate(3)}) {nickname | default(\"n/a\")}")
+///     .to_string(|query| match query {
+///         "name" => Ok("Alice"),
+///         "title" => Ok("Doctor"),
+///         "nickname" => Ok(""),
+///         _ => Err(format!("Can't answer {query:?}")),
+///     })
+///     .unwrap();
+/// assert_eq!(output, "ALICE (Doc) n/a");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FilteredQueryParser<'r> {
+    pub registry: &'r FilterRegistry,
+}
+pub type Parser<'r> = FilteredQueryParser<'r>;
+
+impl<'r> Parser<'r> {
+    /// Create a parser that looks up filters in `registry`.
+    pub fn new(registry: &'r FilterRegistry) -> Self {
+        Parser { registry }
+    }
+}
+
+pub type ParseOutput<'a> = FilteredQuery<'a>;
+
+/// A query with a chain of filters.
+#[derive(Debug, Clone, Copy)]
+pub struct FilteredQuery<'a> {
+    query: &'a str,
+    filters: &'a str,
+    registry: &'a FilterRegistry,
+}
+
+impl<'a> FilteredQuery<'a> {
+    /// The query to be sent to the responder.
+    pub fn query(&self) -> &'a str {
+        self.query
+    }
+
+    /// The filters to be applied to the response, from left to right.
+    pub fn filters(&self) -> FilterCalls<'a> {
+        FilterCalls { text: self.filters }
+    }
+}
+
+/// A filter with its arguments, such as `truncate(10)`.
+#[derive(Debug, Clone, Copy)]
+pub struct FilterCall<'a> {
+    pub name: &'a str,
+    pub args: FilterArgs<'a>,
+}
+
+/// Iterator over the [filter calls](FilterCall) of a [`FilteredQuery`].
+#[derive(Debug, Clone, Copy)]
+pub struct FilterCalls<'a> {
+    text: &'a str,
+}
+
+impl<'a> Iterator for FilterCalls<'a> {
+    type Item = FilterCall<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.text.is_empty() {
+            return None;
+        }
+        let (call, rest) = split_pipe(self.text);
+        self.text = rest.unwrap_or_default();
+        parse_filter_call(call)
+    }
+}
+
+#[derive(Debug, Display, Error, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseError<'a> {
+    #[display("Unexpected token {_0:?}")]
+    UnexpectedToken(Cow<'static, str>, Span),
+    #[display("Unexpected end of input")]
+    UnexpectedEndOfInput(#[error(not(source))] Span),
+    #[display("Invalid filter call {_0:?}")]
+    InvalidFilterCall(Cow<'a, str>, Span),
+    #[display("Unknown filter {_0:?}")]
+    UnknownFilter(Cow<'a, str>, Span),
+    #[display("Invalid arguments of filter {_0:?}: {_1}")]
+    InvalidArguments(Cow<'a, str>, #[error(source)] FilterArgsError, Span),
+}
+
+impl GetSpan for ParseError<'_> {
+    fn span(&self) -> Span {
+        match self {
+            ParseError::UnexpectedToken(_, span)
+            | ParseError::UnexpectedEndOfInput(span)
+            | ParseError::InvalidFilterCall(_, span)
+            | ParseError::UnknownFilter(_, span)
+            | ParseError::InvalidArguments(_, _, span) => *span,
+        }
+    }
+}
+
+impl IntoOwned for ParseError<'_> {
+    type Owned = ParseError<'static>;
+    fn into_owned(self) -> Self::Owned {
+        let own = |name: Cow<str>| Cow::Owned(name.into_owned());
+        match self {
+            ParseError::UnexpectedToken(token, span) => ParseError::UnexpectedToken(token, span),
+            ParseError::UnexpectedEndOfInput(span) => ParseError::UnexpectedEndOfInput(span),
+            ParseError::InvalidFilterCall(call, span) => {
+                ParseError::InvalidFilterCall(own(call), span)
+            }
+            ParseError::UnknownFilter(name, span) => ParseError::UnknownFilter(own(name), span),
+            ParseError::InvalidArguments(name, error, span) => {
+                ParseError::InvalidArguments(own(name), error, span)
+            }
+        }
+    }
+}
+
+impl<'a, 'r: 'a> Parse<'a, ParserInput<'a>> for Parser<'r> {
+    type Output = ParseOutput<'a>;
+    type Error = Option<ParseError<'a>>;
+
+    fn parse(&self, input: ParserInput<'a>) -> Result<(Self::Output, &'a str), Self::Error> {
+        let close_bracket = input.config.close_bracket();
+
+        let tail = input.strip_open_bracket(ParseError::UnexpectedToken)?;
+
+        let close_index = find_unquoted(tail, close_bracket)
+            .ok_or_else(|| ParseError::UnexpectedEndOfInput(Span::empty(input.span_to("").end)))
+            .map_err(Some)?;
+        let body = &tail[..close_index];
+        let rest = &tail[(close_index + close_bracket.len())..];
+
+        let (query, filters) = split_pipe(body);
+        self.check_filters(input, filters).map_err(Some)?;
+
+        let query = FilteredQuery {
+            query: query.trim(),
+            filters: filters.unwrap_or_default(),
+            registry: self.registry,
+        };
+        Ok((query, rest))
+    }
+}
+
+impl<'r> Parser<'r> {
+    /// Check that every filter call is well-formed and refers to a registered filter.
+    fn check_filters<'a>(
+        &self,
+        input: ParserInput<'a>,
+        mut filters: Option<&'a str>,
+    ) -> Result<(), ParseError<'a>> {
+        while let Some(text) = filters {
+            let (call_text, rest) = split_pipe(text);
+            filters = rest;
+
+            let call_text = call_text.trim();
+            let call = parse_filter_call(call_text).ok_or_else(|| {
+                ParseError::InvalidFilterCall(call_text.into(), input.span_of(call_text))
+            })?;
+
+            let filter = self.registry.get(call.name).ok_or_else(|| {
+                ParseError::UnknownFilter(call.name.into(), input.span_of(call.name))
+            })?;
+
+            filter.check(call.args).map_err(|error| {
+                ParseError::InvalidArguments(call.name.into(), error, input.span_of(call_text))
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Find `pattern` outside of string literals.
+fn find_unquoted(text: &str, pattern: &str) -> Option<usize> {
+    let mut quoted = false;
+    for (index, char) in text.char_indices() {
+        if char == '"' {
+            quoted = !quoted;
+        } else if !quoted && text[index..].starts_with(pattern) {
+            return Some(index);
+        }
+    }
+    None
+}
+
+/// Split `text` at the first pipe character outside of string literals.
+fn split_pipe(text: &str) -> (&str, Option<&str>) {
+    match find_unquoted(text, "|") {
+        Some(index) => (&text[..index], Some(&text[(index + 1)..])),
+        None => (text, None),
+    }
+}
+
+/// Parse a filter call such as `upper` or `truncate(10)`.
+fn parse_filter_call(text: &str) -> Option<FilterCall<'_>> {
+    let text = text.trim();
+    let name_end = text
+        .find(|char: char| !char.is_alphanumeric() && char != '_')
+        .unwrap_or(text.len());
+    let (name, args) = text.split_at(name_end);
+    if name.is_empty() {
+        return None;
+    }
+    let args = match args.trim_start() {
+        "" => "",
+        args => args.strip_prefix('(')?.strip_suffix(')')?,
+    };
+    let args = FilterArgs::new(args)?;
+    Some(FilterCall { name, args })
+}
+
+impl<'a, Respond, Output, Error> Render<Respond, String, Error> for FilteredQuery<'a>
+where
+    Respond: FnMut(&'a str) -> Result<Output, Error>,
+    Output: fmt::Display,
+{
+    fn render(self, respond: &mut Respond) -> Result<String, Error> {
+        let response = respond(self.query)?.to_string();
+        Ok(self.apply_filters(response))
+    }
+}
+
+impl<'a, Respond, QueryFuture, Output, Error> AsyncRender<Respond, String, Error>
+    for FilteredQuery<'a>
+where
+    Respond: FnMut(&'a str) -> QueryFuture,
+    QueryFuture: Future<Output = Result<Output, Error>>,
+    Output: fmt::Display,
+{
+    async fn render_async(self, respond: &mut Respond) -> Result<String, Error> {
+        let response = respond(self.query).await?.to_string();
+        Ok(self.apply_filters(response))
+    }
+}
+
+impl FilteredQuery<'_> {
+    /// Pass `response` through the filters from left to right.
+    fn apply_filters(&self, response: String) -> String {
+        self.filters().fold(response, |response, call| {
+            self.registry
+                .get(call.name)
+                .expect("filters should have been checked by the parser")
+                .apply(response, call.args)
+        })
+    }
+}
+
+/// Owned counterpart of [`FilteredQuery`], see [`IntoOwned`].
+///
+/// The filters are taken from the [`FilterRegistry`], so the owned query no longer borrows it.
+#[derive(Clone)]
+pub struct OwnedFilteredQuery {
+    query: OwnedSimpleQuery,
+    filters: Arc<str>,
+    resolved_filters: Arc<[Arc<dyn Filter>]>,
+}
+
+impl OwnedFilteredQuery {
+    /// The query to be sent to the responder.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// The filters to be applied to the response, from left to right.
+    pub fn filters(&self) -> FilterCalls<'_> {
+        FilterCalls {
+            text: &self.filters,
+        }
+    }
+
+    /// Pass `response` through the filters from left to right.
+    fn apply_filters(&self, response: String) -> String {
+        self.filters()
+            .zip(self.resolved_filters.iter())
+            .fold(response, |response, (call, filter)| {
+                filter.apply(response, call.args)
+            })
+    }
+}
+
+impl fmt::Debug for OwnedFilteredQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedFilteredQuery")
+            .field("query", &self.query)
+            .field("filters", &self.filters)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IntoOwned for FilteredQuery<'_> {
+    type Owned = OwnedFilteredQuery;
+    fn into_owned(self) -> Self::Owned {
+        let resolved_filters = self
+            .filters()
+            .map(|call| {
+                self.registry
+                    .get_shared(call.name)
+                    .expect("filters should have been checked by the parser")
+            })
+            .collect();
+        OwnedFilteredQuery {
+            query: self.query.into_owned(),
+            filters: self.filters.into_owned(),
+            resolved_filters,
+        }
+    }
+}
+
+impl<Respond, Output, Error> Render<Respond, String, Error> for OwnedFilteredQuery
+where
+    Respond: FnMut(OwnedSimpleQuery) -> Result<Output, Error>,
+    Output: fmt::Display,
+{
+    fn render(self, respond: &mut Respond) -> Result<String, Error> {
+        let response = respond(self.query.clone())?.to_string();
+        Ok(self.apply_filters(response))
+    }
+}
+
+impl<Respond, QueryFuture, Output, Error> AsyncRender<Respond, String, Error> for OwnedFilteredQuery
+where
+    Respond: FnMut(OwnedSimpleQuery) -> QueryFuture,
+    QueryFuture: Future<Output = Result<Output, Error>>,
+    Output: fmt::Display,
+{
+    async fn render_async(self, respond: &mut Respond) -> Result<String, Error> {
+        let response = respond(self.query.clone()).await?.to_string();
+        Ok(self.apply_filters(response))
+    }
+}
@@ -0,0 +1,84 @@
+use alloc::borrow::Cow;
+use derive_more::{Display, Error};
+
+/// Argument of a [filter call](super::FilterCall), such as `10` in `truncate(10)` or `"n/a"` in `default("n/a")`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterArg<'a> {
+    Integer(i64),
+    String(&'a str),
+}
+
+/// Iterator over the [arguments](FilterArg) of a [filter call](super::FilterCall).
+#[derive(Debug, Clone, Copy)]
+pub struct FilterArgs<'a> {
+    text: &'a str,
+}
+
+impl<'a> FilterArgs<'a> {
+    /// Create the arguments from the text between the parentheses of a filter call.
+    ///
+    /// Return `None` if the text is not a valid list of arguments.
+    pub fn new(text: &'a str) -> Option<Self> {
+        let args = FilterArgs { text };
+        let mut rest = text;
+        while let Some((_, tail)) = split_first_arg(rest)? {
+            rest = tail;
+        }
+        Some(args)
+    }
+
+    /// Get the argument at `index`.
+    pub fn get(mut self, index: usize) -> Option<FilterArg<'a>> {
+        self.nth(index)
+    }
+}
+
+impl<'a> Iterator for FilterArgs<'a> {
+    type Item = FilterArg<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (arg, rest) = split_first_arg(self.text)??;
+        self.text = rest;
+        Some(arg)
+    }
+}
+
+/// Split the first argument from the rest.
+///
+/// Return `None` if the syntax is invalid, or `Some(None)` if there are no more arguments.
+fn split_first_arg(text: &str) -> Option<Option<(FilterArg<'_>, &str)>> {
+    let text = text.trim_start();
+    if text.is_empty() {
+        return Some(None);
+    }
+
+    let (arg, rest) = if let Some(tail) = text.strip_prefix('"') {
+        let quote_index = tail.find('"')?;
+        let rest = tail[(quote_index + 1)..].trim_start();
+        (FilterArg::String(&tail[..quote_index]), rest)
+    } else {
+        let comma_index = text.find(',').unwrap_or(text.len());
+        let integer = text[..comma_index].trim().parse().ok()?;
+        (FilterArg::Integer(integer), &text[comma_index..])
+    };
+
+    if rest.is_empty() {
+        return Some(Some((arg, rest)));
+    }
+
+    let rest = rest.strip_prefix(',')?;
+    Some(Some((arg, rest)))
+}
+
+/// Error returned by [`Filter::check`](super::Filter::check).
+#[derive(Debug, Display, Error, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FilterArgsError {
+    #[display("Expected {expected} argument(s), but received {received}")]
+    Count { expected: usize, received: usize },
+    #[display("Argument at {index} should be {expected}")]
+    Type {
+        index: usize,
+        expected: Cow<'static, str>,
+    },
+}
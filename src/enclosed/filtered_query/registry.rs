@@ -0,0 +1,163 @@
+use super::{FilterArg, FilterArgs, FilterArgsError};
+use std::{collections::HashMap, fmt, sync::Arc};
+
+/// Transformation of a response, such as `upper` in `{name | upper}`.
+pub trait Filter: Send + Sync {
+    /// Check the arguments of a filter call when the template is parsed.
+    fn check(&self, args: FilterArgs<'_>) -> Result<(), FilterArgsError>;
+    /// Transform the response of a query.
+    ///
+    /// The arguments are guaranteed to have passed [`check`](Filter::check).
+    fn apply(&self, input: String, args: FilterArgs<'_>) -> String;
+}
+
+/// Collection of named [filters](Filter) for [`FilteredQueryParser`](super::FilteredQueryParser).
+#[derive(Default)]
+pub struct FilterRegistry {
+    filters: HashMap<String, Arc<dyn Filter>>,
+}
+
+impl FilterRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        FilterRegistry::default()
+    }
+
+    /// Create a registry of the built-in filters:
+    /// * `upper`: Convert to uppercase.
+    /// * `lower`: Convert to lowercase.
+    /// * `trim`: Remove leading and trailing whitespaces.
+    /// * `truncate(n)`: Keep only the first `n` characters.
+    /// * `default("text")`: Replace an empty response with `text`.
+    pub fn builtin() -> Self {
+        FilterRegistry::new()
+            .with_filter("upper", Uppercase)
+            .with_filter("lower", Lowercase)
+            .with_filter("trim", Trim)
+            .with_filter("truncate", Truncate)
+            .with_filter("default", Fallback)
+    }
+
+    /// Add a filter, replacing the existing filter of the same name.
+    pub fn with_filter(mut self, name: impl Into<String>, filter: impl Filter + 'static) -> Self {
+        self.insert(name, filter);
+        self
+    }
+
+    /// Add a filter, return the existing filter of the same name.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        filter: impl Filter + 'static,
+    ) -> Option<Arc<dyn Filter>> {
+        self.filters.insert(name.into(), Arc::new(filter))
+    }
+
+    /// Get a filter by name.
+    pub fn get(&self, name: &str) -> Option<&dyn Filter> {
+        self.filters.get(name).map(Arc::as_ref)
+    }
+
+    /// Get a shared filter by name, for queries that outlive the registry.
+    pub(super) fn get_shared(&self, name: &str) -> Option<Arc<dyn Filter>> {
+        self.filters.get(name).cloned()
+    }
+}
+
+impl fmt::Debug for FilterRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.filters.keys()).finish()
+    }
+}
+
+fn check_count(args: FilterArgs<'_>, expected: usize) -> Result<(), FilterArgsError> {
+    let received = args.count();
+    if received != expected {
+        return Err(FilterArgsError::Count { expected, received });
+    }
+    Ok(())
+}
+
+struct Uppercase;
+
+impl Filter for Uppercase {
+    fn check(&self, args: FilterArgs<'_>) -> Result<(), FilterArgsError> {
+        check_count(args, 0)
+    }
+
+    fn apply(&self, input: String, _: FilterArgs<'_>) -> String {
+        input.to_uppercase()
+    }
+}
+
+struct Lowercase;
+
+impl Filter for Lowercase {
+    fn check(&self, args: FilterArgs<'_>) -> Result<(), FilterArgsError> {
+        check_count(args, 0)
+    }
+
+    fn apply(&self, input: String, _: FilterArgs<'_>) -> String {
+        input.to_lowercase()
+    }
+}
+
+struct Trim;
+
+impl Filter for Trim {
+    fn check(&self, args: FilterArgs<'_>) -> Result<(), FilterArgsError> {
+        check_count(args, 0)
+    }
+
+    fn apply(&self, input: String, _: FilterArgs<'_>) -> String {
+        input.trim().to_string()
+    }
+}
+
+struct Truncate;
+
+impl Filter for Truncate {
+    fn check(&self, args: FilterArgs<'_>) -> Result<(), FilterArgsError> {
+        check_count(args, 1)?;
+        match args.get(0) {
+            Some(FilterArg::Integer(length)) if length >= 0 => Ok(()),
+            _ => Err(FilterArgsError::Type {
+                index: 0,
+                expected: "a non-negative integer".into(),
+            }),
+        }
+    }
+
+    fn apply(&self, mut input: String, args: FilterArgs<'_>) -> String {
+        let Some(FilterArg::Integer(length)) = args.get(0) else {
+            return input;
+        };
+        let length = usize::try_from(length).unwrap_or(usize::MAX);
+        if let Some((index, _)) = input.char_indices().nth(length) {
+            input.truncate(index);
+        }
+        input
+    }
+}
+
+struct Fallback;
+
+impl Filter for Fallback {
+    fn check(&self, args: FilterArgs<'_>) -> Result<(), FilterArgsError> {
+        check_count(args, 1)?;
+        match args.get(0) {
+            Some(FilterArg::String(_)) => Ok(()),
+            _ => Err(FilterArgsError::Type {
+                index: 0,
+                expected: "a string".into(),
+            }),
+        }
+    }
+
+    fn apply(&self, input: String, args: FilterArgs<'_>) -> String {
+        match args.get(0) {
+            Some(FilterArg::String(default)) if input.is_empty() => default.to_string(),
+            _ => input,
+        }
+    }
+}
@@ -0,0 +1,564 @@
+use super::ComponentParserInput;
+use crate::{AsyncRender, GetSpan, Parse, Render, Span};
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use core::{
+    fmt::{self, Write},
+    future::Future,
+    str::FromStr,
+};
+use derive_more::{Display, Error};
+
+#[cfg(feature = "std")]
+use super::OwnedSimpleQuery;
+#[cfg(feature = "std")]
+use crate::IntoOwned;
+
+pub type ParserInput<'a> = ComponentParserInput<'a>;
+
+/// Parse queries followed by format specifications that mirror [`core::fmt`], such as `{price:>10.2}`.
+///
+/// The format specification is placed after the last colon of the query. Queries that contain colons must therefore
+/// end with a colon, such as `{env:HOME:}`.
+///
+/// ```
+/// # #[cfg(not(feature = "std"))] fn main() {}
+/// # #[cfg(feature = "std")] fn main() {
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{
+///     enclosed::{FormattedQueryParser, Parser, SimpleEscapeParser},
+///     IntoTemplateSystem,
+/// };
+/// let output = Parser::curly_braces()
+///     .with_escape_parser(SimpleEscapeParser)
+///     .with_query_parser(FormattedQueryParser)
+///     .into_template_system::<&str>()
+///     .lazy_parse("[{price:*^+10.2}] [{count:05}] [{color:#x}]")
+///     .to_string(|query| match query {
+///         "price" => Ok(3.14159),
+///         "count" => Ok(42.0),
+///         "color" => Ok(255.0),
+///         _ => Err(format!("Can't answer {query:?}")),
+///     })
+///     .unwrap();
+/// assert_eq!(output, "[**+3.14***] [00042] [0xff]");
+/// # }
+/// ```
+///
+/// The format specification is applied to the [`Display`](fmt::Display) implementation of the response, so precision
+/// rounds a floating-point response but truncates a string response. Likewise, a width without alignment pads a
+/// numeric response on the left and a string response on the right, as long as the [`Display`](fmt::Display)
+/// implementation of the response respects the flags of the [`fmt::Formatter`]. Integer types (`x`, `X`, `o`, `b`) and exponent
+/// types (`e`, `E`) require the displayed response to be a non-negative integer or a number respectively, otherwise
+/// rendering fails with [`RenderError::NotANumber`].
+#[derive(Debug, Clone, Copy)]
+pub struct FormattedQueryParser;
+pub type Parser = FormattedQueryParser;
+
+pub type ParseOutput<'a> = FormattedQuery<'a>;
+
+/// A query with a format specification.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormattedQuery<'a> {
+    pub query: &'a str,
+    pub spec: FormatSpec,
+}
+
+/// Parsed format specification, such as `>10.2` in `{price:>10.2}`.
+///
+/// The syntax is `[[fill]align][sign]['#']['0'][width]['.' precision][type]`, like that of [`core::fmt`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormatSpec {
+    pub fill: char,
+    pub align: Option<Align>,
+    pub sign: Option<Sign>,
+    pub alternate: bool,
+    pub zero: bool,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+    pub kind: FormatKind,
+}
+
+/// Alignment of a [`FormatSpec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Align {
+    /// `<`
+    Left,
+    /// `^`
+    Center,
+    /// `>`
+    Right,
+}
+
+/// Sign flag of a [`FormatSpec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Sign {
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+}
+
+/// Type of a [`FormatSpec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FormatKind {
+    /// No type, i.e. [`fmt::Display`].
+    Display,
+    /// `x`, i.e. [`fmt::LowerHex`].
+    LowerHex,
+    /// `X`, i.e. [`fmt::UpperHex`].
+    UpperHex,
+    /// `o`, i.e. [`fmt::Octal`].
+    Octal,
+    /// `b`, i.e. [`fmt::Binary`].
+    Binary,
+    /// `e`, i.e. [`fmt::LowerExp`].
+    LowerExp,
+    /// `E`, i.e. [`fmt::UpperExp`].
+    UpperExp,
+}
+
+impl Default for FormatSpec {
+    fn default() -> Self {
+        FormatSpec {
+            fill: ' ',
+            align: None,
+            sign: None,
+            alternate: false,
+            zero: false,
+            width: None,
+            precision: None,
+            kind: FormatKind::Display,
+        }
+    }
+}
+
+impl FormatSpec {
+    /// Parse a format specification, return `None` if it is invalid.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut spec = FormatSpec::default();
+        let mut rest = text;
+
+        let parse_align = |char| match char {
+            '<' => Some(Align::Left),
+            '^' => Some(Align::Center),
+            '>' => Some(Align::Right),
+            _ => None,
+        };
+        let mut chars = rest.chars();
+        match (chars.next(), chars.next().and_then(parse_align)) {
+            (Some(fill), Some(align)) => {
+                spec.fill = fill;
+                spec.align = Some(align);
+                rest = chars.as_str();
+            }
+            (Some(char), _) if parse_align(char).is_some() => {
+                spec.align = parse_align(char);
+                rest = &rest[1..];
+            }
+            _ => {}
+        }
+
+        if let Some(tail) = rest.strip_prefix('+') {
+            spec.sign = Some(Sign::Plus);
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix('-') {
+            spec.sign = Some(Sign::Minus);
+            rest = tail;
+        }
+
+        if let Some(tail) = rest.strip_prefix('#') {
+            spec.alternate = true;
+            rest = tail;
+        }
+
+        if let Some(tail) = rest.strip_prefix('0') {
+            spec.zero = true;
+            rest = tail;
+        }
+
+        let (width, tail) = split_integer(rest)?;
+        spec.width = width;
+        rest = tail;
+
+        if let Some(tail) = rest.strip_prefix('.') {
+            let (precision, tail) = split_integer(tail)?;
+            spec.precision = Some(precision?);
+            rest = tail;
+        }
+
+        spec.kind = match rest {
+            "" => FormatKind::Display,
+            "x" => FormatKind::LowerHex,
+            "X" => FormatKind::UpperHex,
+            "o" => FormatKind::Octal,
+            "b" => FormatKind::Binary,
+            "e" => FormatKind::LowerExp,
+            "E" => FormatKind::UpperExp,
+            _ => return None,
+        };
+
+        Some(spec)
+    }
+}
+
+/// Split the leading decimal integer (if any) from the rest, return `None` if the integer overflows.
+fn split_integer(text: &str) -> Option<(Option<usize>, &str)> {
+    let end = text
+        .find(|char: char| !char.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (digits, rest) = text.split_at(end);
+    if digits.is_empty() {
+        return Some((None, rest));
+    }
+    let integer = digits.parse().ok()?;
+    Some((Some(integer), rest))
+}
+
+#[derive(Debug, Display, Error, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseError {
+    #[display("Unexpected token {_0:?}")]
+    UnexpectedToken(Cow<'static, str>, Span),
+    #[display("Unexpected end of input")]
+    UnexpectedEndOfInput(#[error(not(source))] Span),
+    #[display("Invalid format specification")]
+    InvalidFormatSpec(#[error(not(source))] Span),
+}
+
+impl GetSpan for ParseError {
+    fn span(&self) -> Span {
+        match self {
+            ParseError::UnexpectedToken(_, span)
+            | ParseError::UnexpectedEndOfInput(span)
+            | ParseError::InvalidFormatSpec(span) => *span,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl IntoOwned for ParseError {
+    type Owned = Self;
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
+}
+
+impl<'a> Parse<'a, ParserInput<'a>> for Parser {
+    type Output = ParseOutput<'a>;
+    type Error = Option<ParseError>;
+
+    fn parse(&self, input: ParserInput<'a>) -> Result<(Self::Output, &'a str), Self::Error> {
+        let close_bracket = input.config.close_bracket();
+
+        let tail = input.strip_open_bracket(ParseError::UnexpectedToken)?;
+
+        let close_index = tail
+            .find(close_bracket)
+            .ok_or_else(|| ParseError::UnexpectedEndOfInput(Span::empty(input.span_to("").end)))
+            .map_err(Some)?;
+        let body = &tail[..close_index];
+        let rest = &tail[(close_index + close_bracket.len())..];
+
+        let Some((query, spec)) = body.rsplit_once(':') else {
+            let spec = FormatSpec::default();
+            return Ok((FormattedQuery { query: body, spec }, rest));
+        };
+
+        let spec = FormatSpec::parse(spec)
+            .ok_or_else(|| ParseError::InvalidFormatSpec(input.span_of(spec)))
+            .map_err(Some)?;
+        Ok((FormattedQuery { query, spec }, rest))
+    }
+}
+
+/// Write `$value` with `$width` and the sign, alternate, zero, and precision of `$spec`, and the type `$kind`.
+macro_rules! write_with_flags {
+    ($out:expr, $value:expr, $spec:expr, $width:expr, $kind:literal) => {{
+        let plus = $spec.sign == Some(Sign::Plus);
+        let w = $width;
+        match (plus, $spec.alternate, $spec.zero, $spec.precision) {
+            (false, false, false, None) => write!($out, concat!("{:w$", $kind, "}"), $value, w = w),
+            (false, false, false, Some(p)) => {
+                write!($out, concat!("{:w$.p$", $kind, "}"), $value, w = w, p = p)
+            }
+            (false, false, true, None) => write!($out, concat!("{:0w$", $kind, "}"), $value, w = w),
+            (false, false, true, Some(p)) => {
+                write!($out, concat!("{:0w$.p$", $kind, "}"), $value, w = w, p = p)
+            }
+            (false, true, false, None) => write!($out, concat!("{:#w$", $kind, "}"), $value, w = w),
+            (false, true, false, Some(p)) => {
+                write!($out, concat!("{:#w$.p$", $kind, "}"), $value, w = w, p = p)
+            }
+            (false, true, true, None) => write!($out, concat!("{:#0w$", $kind, "}"), $value, w = w),
+            (false, true, true, Some(p)) => {
+                write!($out, concat!("{:#0w$.p$", $kind, "}"), $value, w = w, p = p)
+            }
+            (true, false, false, None) => write!($out, concat!("{:+w$", $kind, "}"), $value, w = w),
+            (true, false, false, Some(p)) => {
+                write!($out, concat!("{:+w$.p$", $kind, "}"), $value, w = w, p = p)
+            }
+            (true, false, true, None) => write!($out, concat!("{:+0w$", $kind, "}"), $value, w = w),
+            (true, false, true, Some(p)) => {
+                write!($out, concat!("{:+0w$.p$", $kind, "}"), $value, w = w, p = p)
+            }
+            (true, true, false, None) => write!($out, concat!("{:+#w$", $kind, "}"), $value, w = w),
+            (true, true, false, Some(p)) => {
+                write!($out, concat!("{:+#w$.p$", $kind, "}"), $value, w = w, p = p)
+            }
+            (true, true, true, None) => write!($out, concat!("{:+#0w$", $kind, "}"), $value, w = w),
+            (true, true, true, Some(p)) => write!(
+                $out,
+                concat!("{:+#0w$.p$", $kind, "}"),
+                $value,
+                w = w,
+                p = p
+            ),
+        }
+    }};
+}
+use write_with_flags;
+
+/// Returned upon the [rendering](Render) of a [`FormattedQuery`].
+///
+/// The [`FormatSpec`] is applied when it is [displayed](fmt::Display).
+#[derive(Debug, Clone, Copy)]
+pub struct Formatted<Output> {
+    pub value: FormattedValue<Output>,
+    pub spec: FormatSpec,
+}
+
+/// Response of a [`FormattedQuery`], converted as the [type](FormatKind) of its [`FormatSpec`] requires.
+#[derive(Debug, Clone, Copy)]
+pub enum FormattedValue<Output> {
+    /// The response itself, for [`FormatKind::Display`].
+    Response(Output),
+    /// The response parsed as a non-negative integer, for `x`, `X`, `o`, and `b`.
+    Integer(u128),
+    /// The response parsed as a number, for `e` and `E`.
+    Float(f64),
+}
+
+/// Error type of [`Render`] on [`FormattedQuery`] and [`OwnedFormattedQuery`].
+#[derive(Debug, Display, Error, Clone, PartialEq, Eq)]
+pub enum RenderError<QueryError> {
+    /// The respond function failed to answer the query.
+    #[display("{_0}")]
+    Query(QueryError),
+    /// The type of the format specification requires a number, but the response to the query is not one.
+    #[display("Response to {_0:?} is not a number of the requested type")]
+    NotANumber(#[error(not(source))] Cow<'static, str>),
+}
+
+impl<Output: fmt::Display> Formatted<Output> {
+    /// Convert `output` as the type of `spec` requires, or return `None` if it requires a number that `output` is not.
+    pub fn new(output: Output, spec: FormatSpec) -> Option<Self> {
+        let value = match spec.kind {
+            FormatKind::Display => FormattedValue::Response(output),
+            FormatKind::LowerHex
+            | FormatKind::UpperHex
+            | FormatKind::Octal
+            | FormatKind::Binary => FormattedValue::Integer(parse_number(&output)?),
+            FormatKind::LowerExp | FormatKind::UpperExp => {
+                FormattedValue::Float(parse_number(&output)?)
+            }
+        };
+        Some(Formatted { value, spec })
+    }
+}
+
+/// Format the response to `query` as `spec` requires.
+fn format<QueryError, Output: fmt::Display>(
+    query: &str,
+    spec: FormatSpec,
+    output: Output,
+) -> Result<Formatted<Output>, RenderError<QueryError>> {
+    Formatted::new(output, spec).ok_or_else(|| RenderError::NotANumber(query.to_string().into()))
+}
+
+impl<'a, Respond, Output, Error> Render<Respond, Formatted<Output>, RenderError<Error>>
+    for FormattedQuery<'a>
+where
+    Respond: FnMut(&'a str) -> Result<Output, Error>,
+    Output: fmt::Display,
+{
+    fn render(self, respond: &mut Respond) -> Result<Formatted<Output>, RenderError<Error>> {
+        let output = respond(self.query).map_err(RenderError::Query)?;
+        format(self.query, self.spec, output)
+    }
+}
+
+impl<'a, Respond, QueryFuture, Output, Error>
+    AsyncRender<Respond, Formatted<Output>, RenderError<Error>> for FormattedQuery<'a>
+where
+    Respond: FnMut(&'a str) -> QueryFuture,
+    QueryFuture: Future<Output = Result<Output, Error>>,
+    Output: fmt::Display,
+{
+    async fn render_async(
+        self,
+        respond: &mut Respond,
+    ) -> Result<Formatted<Output>, RenderError<Error>> {
+        let output = respond(self.query).await.map_err(RenderError::Query)?;
+        format(self.query, self.spec, output)
+    }
+}
+
+/// Owned counterpart of [`FormattedQuery`], see [`IntoOwned`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedFormattedQuery {
+    pub query: OwnedSimpleQuery,
+    pub spec: FormatSpec,
+}
+
+#[cfg(feature = "std")]
+impl IntoOwned for FormattedQuery<'_> {
+    type Owned = OwnedFormattedQuery;
+    fn into_owned(self) -> Self::Owned {
+        OwnedFormattedQuery {
+            query: self.query.into_owned(),
+            spec: self.spec,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Respond, Output, Error> Render<Respond, Formatted<Output>, RenderError<Error>>
+    for OwnedFormattedQuery
+where
+    Respond: FnMut(OwnedSimpleQuery) -> Result<Output, Error>,
+    Output: fmt::Display,
+{
+    fn render(self, respond: &mut Respond) -> Result<Formatted<Output>, RenderError<Error>> {
+        let output = respond(self.query.clone()).map_err(RenderError::Query)?;
+        format(&self.query, self.spec, output)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Respond, QueryFuture, Output, Error>
+    AsyncRender<Respond, Formatted<Output>, RenderError<Error>> for OwnedFormattedQuery
+where
+    Respond: FnMut(OwnedSimpleQuery) -> QueryFuture,
+    QueryFuture: Future<Output = Result<Output, Error>>,
+    Output: fmt::Display,
+{
+    async fn render_async(
+        self,
+        respond: &mut Respond,
+    ) -> Result<Formatted<Output>, RenderError<Error>> {
+        let output = respond(self.query.clone())
+            .await
+            .map_err(RenderError::Query)?;
+        format(&self.query, self.spec, output)
+    }
+}
+
+impl<Output: fmt::Display> fmt::Display for Formatted<Output> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let spec = &self.spec;
+        let (Some(width), Some(align), false) = (spec.width, spec.align, spec.zero) else {
+            return self.write_with_width(f, spec.width.unwrap_or(0));
+        };
+
+        let mut counter = CharCounter(0);
+        self.write_with_width(&mut counter, 0)?;
+        let padding = width.saturating_sub(counter.0);
+        let (before, after) = match align {
+            Align::Left => (0, padding),
+            Align::Center => (padding / 2, padding - padding / 2),
+            Align::Right => (padding, 0),
+        };
+
+        (0..before).try_for_each(|_| f.write_char(spec.fill))?;
+        self.write_with_width(f, 0)?;
+        (0..after).try_for_each(|_| f.write_char(spec.fill))
+    }
+}
+
+impl<Output: fmt::Display> Formatted<Output> {
+    /// Write the output with `width` and every part of the format specification except fill and alignment.
+    ///
+    /// Like [`core::fmt`], the written value pads itself to `width`, so numbers are aligned to the right and texts to
+    /// the left.
+    fn write_with_width(&self, out: &mut impl Write, width: usize) -> fmt::Result {
+        let spec = &self.spec;
+        match (&self.value, spec.kind) {
+            (FormattedValue::Response(output), _) => {
+                write_with_flags!(out, output, spec, width, "")
+            }
+            (FormattedValue::Integer(number), FormatKind::LowerHex) => {
+                write_with_flags!(out, number, spec, width, "x")
+            }
+            (FormattedValue::Integer(number), FormatKind::UpperHex) => {
+                write_with_flags!(out, number, spec, width, "X")
+            }
+            (FormattedValue::Integer(number), FormatKind::Octal) => {
+                write_with_flags!(out, number, spec, width, "o")
+            }
+            (FormattedValue::Integer(number), FormatKind::Binary) => {
+                write_with_flags!(out, number, spec, width, "b")
+            }
+            (FormattedValue::Float(number), FormatKind::LowerExp) => {
+                write_with_flags!(out, number, spec, width, "e")
+            }
+            (FormattedValue::Float(number), FormatKind::UpperExp) => {
+                write_with_flags!(out, number, spec, width, "E")
+            }
+            // only reachable when the fields are set by hand
+            (FormattedValue::Integer(number), _) => write_with_flags!(out, number, spec, width, ""),
+            (FormattedValue::Float(number), _) => write_with_flags!(out, number, spec, width, ""),
+        }
+    }
+}
+
+/// Parse the displayed `output` as a number.
+fn parse_number<Number: FromStr>(output: &impl fmt::Display) -> Option<Number> {
+    let mut buffer = NumberBuffer {
+        bytes: [0; NUMBER_BUFFER_SIZE],
+        len: 0,
+    };
+    write!(buffer, "{output}").ok()?;
+    core::str::from_utf8(&buffer.bytes[..buffer.len])
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Count the number of characters written.
+struct CharCounter(usize);
+
+impl Write for CharCounter {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.0 += text.chars().count();
+        Ok(())
+    }
+}
+
+const NUMBER_BUFFER_SIZE: usize = 64;
+
+/// Fixed-size buffer to hold the displayed output of a numeric response.
+struct NumberBuffer {
+    bytes: [u8; NUMBER_BUFFER_SIZE],
+    len: usize,
+}
+
+impl Write for NumberBuffer {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let end = self.len + text.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(text.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
@@ -0,0 +1,330 @@
+use super::{simple_query, ComponentParserInput};
+use crate::{Parse, Span};
+use pipe_trait::Pipe;
+
+#[cfg(feature = "std")]
+use super::OwnedSimpleQuery;
+#[cfg(feature = "std")]
+use crate::{AsyncRender, IntoOwned, Render};
+#[cfg(feature = "std")]
+use core::{
+    fmt::{self, Write},
+    future::Future,
+};
+#[cfg(feature = "std")]
+use derive_more::{Display, Error};
+#[cfg(feature = "std")]
+use std::{borrow::Cow, sync::Arc};
+
+pub type ParserInput<'a> = ComponentParserInput<'a>;
+
+/// Parse queries which may contain other queries, such as `{file:{HOME}/notes.txt}`.
+///
+/// The inner queries are sent to the responder first, their outputs are then joined with the rest of the query to
+/// form the outer query:
+///
+/// ```
+/// # #[cfg(not(feature = "std"))] fn main() {}
+/// # #[cfg(feature = "std")] fn main() {
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{
+///     enclosed::{NestedQueryParser, Parser, SimpleEscapeParser},
+///     IntoTemplateSystem,
+/// };
+/// use std::borrow::Cow;
+/// let output = Parser::curly_braces()
+///     .with_escape_parser(SimpleEscapeParser)
+///     .with_query_parser(NestedQueryParser)
+///     .into_template_system::<Cow<str>>()
+///     .lazy_parse("Notes: {file:{HOME}/notes.txt}")
+///     .to_string(|query| match query.as_ref() {
+///         "HOME" => Ok("/home/alice"),
+///         "file:/home/alice/notes.txt" => Ok("buy milk"),
+///         _ => Err(format!("Can't answer {query:?}")),
+///     })
+///     .unwrap();
+/// assert_eq!(output, "Notes: buy milk");
+/// # }
+/// ```
+///
+/// Nesting requires the [opening bracket](super::ParserConfig::open_bracket) and the
+/// [closing bracket](super::ParserConfig::close_bracket) to be different.
+#[derive(Debug, Clone, Copy)]
+pub struct NestedQueryParser;
+pub type Parser = NestedQueryParser;
+
+pub type ParseOutput<'a> = NestedQuery<'a>;
+
+pub type ParseError = simple_query::ParseError;
+
+/// A query whose content may contain other queries.
+///
+/// The content is only split into [parts](NestedQuery::parts) on demand.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NestedQuery<'a> {
+    text: &'a str,
+    open_bracket: &'a str,
+    close_bracket: &'a str,
+}
+
+/// Item of [`Parts`].
+#[derive(Debug, Clone, Copy)]
+pub enum NestedQueryPart<'a> {
+    /// Literal text.
+    Text(&'a str),
+    /// An inner query.
+    Query(NestedQuery<'a>),
+}
+
+/// Iterator over the [parts](NestedQueryPart) of a [`NestedQuery`].
+#[derive(Debug, Clone, Copy)]
+pub struct Parts<'a> {
+    text: &'a str,
+    open_bracket: &'a str,
+    close_bracket: &'a str,
+}
+
+impl<'a> NestedQuery<'a> {
+    /// Content of the query between the outermost brackets.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Split the query into literal texts and inner queries.
+    pub fn parts(&self) -> Parts<'a> {
+        Parts {
+            text: self.text,
+            open_bracket: self.open_bracket,
+            close_bracket: self.close_bracket,
+        }
+    }
+}
+
+impl<'a> Iterator for Parts<'a> {
+    type Item = NestedQueryPart<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.text.is_empty() {
+            return None;
+        }
+
+        if let Some(tail) = self.text.strip_prefix(self.open_bracket) {
+            let close_index = find_close_bracket(tail, self.open_bracket, self.close_bracket)?;
+            let query = NestedQuery {
+                text: &tail[..close_index],
+                open_bracket: self.open_bracket,
+                close_bracket: self.close_bracket,
+            };
+            self.text = &tail[(close_index + self.close_bracket.len())..];
+            return query.pipe(NestedQueryPart::Query).pipe(Some);
+        }
+
+        let text_end = self.text.find(self.open_bracket).unwrap_or(self.text.len());
+        let (text, rest) = self.text.split_at(text_end);
+        self.text = rest;
+        text.pipe(NestedQueryPart::Text).pipe(Some)
+    }
+}
+
+impl<'a> Parse<'a, ParserInput<'a>> for Parser {
+    type Output = ParseOutput<'a>;
+    type Error = Option<ParseError>;
+
+    fn parse(&self, input: ParserInput<'a>) -> Result<(Self::Output, &'a str), Self::Error> {
+        let (open_bracket, close_bracket) =
+            (input.config.open_bracket(), input.config.close_bracket());
+
+        let tail = input.strip_open_bracket(ParseError::UnexpectedToken)?;
+
+        let close_index = find_close_bracket(tail, open_bracket, close_bracket)
+            .ok_or_else(|| ParseError::UnexpectedEndOfInput(Span::empty(input.span_to("").end)))
+            .map_err(Some)?;
+        let query = NestedQuery {
+            text: &tail[..close_index],
+            open_bracket,
+            close_bracket,
+        };
+        let rest = &tail[(close_index + close_bracket.len())..];
+        Ok((query, rest))
+    }
+}
+
+/// Find the index of the closing bracket that matches an opening bracket right before `text`.
+fn find_close_bracket(text: &str, open_bracket: &str, close_bracket: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut index = 0;
+    while index < text.len() {
+        let rest = &text[index..];
+        if rest.starts_with(close_bracket) {
+            if depth == 0 {
+                return Some(index);
+            }
+            depth -= 1;
+            index += close_bracket.len();
+        } else if rest.starts_with(open_bracket) {
+            depth += 1;
+            index += open_bracket.len();
+        } else {
+            index += rest.chars().next()?.len_utf8();
+        }
+    }
+    None
+}
+
+/// Error returned by [`NestedQuery::assemble`] and by the [rendering](Render) of a [`NestedQuery`].
+#[cfg(feature = "std")]
+#[derive(Debug, Display, Error, Clone, Copy)]
+pub enum AssembleError<QueryError> {
+    /// The respond function failed to answer a query.
+    Query(QueryError),
+    /// The output of an inner query failed to be displayed.
+    Write(fmt::Error),
+}
+
+#[cfg(feature = "std")]
+impl<'a> NestedQuery<'a> {
+    /// Send the inner queries to `respond`, then join their outputs with the literal texts.
+    ///
+    /// Queries without inner queries are returned as-is without allocation.
+    pub fn assemble<Respond, Output, Error>(
+        self,
+        respond: &mut Respond,
+    ) -> Result<Cow<'a, str>, AssembleError<Error>>
+    where
+        Respond: FnMut(Cow<'a, str>) -> Result<Output, Error>,
+        Output: fmt::Display,
+    {
+        if !self.text.contains(self.open_bracket) {
+            return Ok(Cow::Borrowed(self.text));
+        }
+
+        let mut assembled = String::new();
+        for part in self.parts() {
+            match part {
+                NestedQueryPart::Text(text) => assembled.push_str(text),
+                NestedQueryPart::Query(query) => {
+                    let output = query.render(respond)?;
+                    write!(assembled, "{output}").map_err(AssembleError::Write)?;
+                }
+            }
+        }
+        Ok(Cow::Owned(assembled))
+    }
+
+    /// Asynchronous counterpart of [`assemble`](Self::assemble).
+    pub async fn assemble_async<Respond, QueryFuture, Output, Error>(
+        self,
+        respond: &mut Respond,
+    ) -> Result<Cow<'a, str>, AssembleError<Error>>
+    where
+        Respond: FnMut(Cow<'a, str>) -> QueryFuture,
+        QueryFuture: Future<Output = Result<Output, Error>>,
+        Output: fmt::Display,
+    {
+        if !self.text.contains(self.open_bracket) {
+            return Ok(Cow::Borrowed(self.text));
+        }
+
+        let mut assembled = String::new();
+        for part in self.parts() {
+            match part {
+                NestedQueryPart::Text(text) => assembled.push_str(text),
+                NestedQueryPart::Query(query) => {
+                    let output = Box::pin(query.render_async(respond)).await?;
+                    write!(assembled, "{output}").map_err(AssembleError::Write)?;
+                }
+            }
+        }
+        Ok(Cow::Owned(assembled))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, Respond, Output, Error> Render<Respond, Output, AssembleError<Error>> for NestedQuery<'a>
+where
+    Respond: FnMut(Cow<'a, str>) -> Result<Output, Error>,
+    Output: fmt::Display,
+{
+    fn render(self, respond: &mut Respond) -> Result<Output, AssembleError<Error>> {
+        let query = self.assemble(respond)?;
+        respond(query).map_err(AssembleError::Query)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, Respond, QueryFuture, Output, Error> AsyncRender<Respond, Output, AssembleError<Error>>
+    for NestedQuery<'a>
+where
+    Respond: FnMut(Cow<'a, str>) -> QueryFuture,
+    QueryFuture: Future<Output = Result<Output, Error>>,
+    Output: fmt::Display,
+{
+    async fn render_async(self, respond: &mut Respond) -> Result<Output, AssembleError<Error>> {
+        let query = self.assemble_async(respond).await?;
+        respond(query).await.map_err(AssembleError::Query)
+    }
+}
+
+/// Owned counterpart of [`NestedQuery`], see [`IntoOwned`].
+///
+/// The queries that it assembles are sent to the respond function as [`OwnedSimpleQuery`]s.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedNestedQuery {
+    text: Arc<str>,
+    open_bracket: Arc<str>,
+    close_bracket: Arc<str>,
+}
+
+#[cfg(feature = "std")]
+impl OwnedNestedQuery {
+    /// Borrow the query as a [`NestedQuery`].
+    pub fn as_query(&self) -> NestedQuery<'_> {
+        NestedQuery {
+            text: &self.text,
+            open_bracket: &self.open_bracket,
+            close_bracket: &self.close_bracket,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl IntoOwned for NestedQuery<'_> {
+    type Owned = OwnedNestedQuery;
+    fn into_owned(self) -> Self::Owned {
+        OwnedNestedQuery {
+            text: Arc::from(self.text),
+            open_bracket: Arc::from(self.open_bracket),
+            close_bracket: Arc::from(self.close_bracket),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Respond, Output, Error> Render<Respond, Output, AssembleError<Error>> for OwnedNestedQuery
+where
+    Respond: FnMut(OwnedSimpleQuery) -> Result<Output, Error>,
+    Output: fmt::Display,
+{
+    fn render(self, respond: &mut Respond) -> Result<Output, AssembleError<Error>> {
+        self.as_query()
+            .render(&mut |query: Cow<str>| respond(Arc::from(query)))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Respond, QueryFuture, Output, Error> AsyncRender<Respond, Output, AssembleError<Error>>
+    for OwnedNestedQuery
+where
+    Respond: FnMut(OwnedSimpleQuery) -> QueryFuture,
+    QueryFuture: Future<Output = Result<Output, Error>>,
+    Output: fmt::Display,
+{
+    async fn render_async(self, respond: &mut Respond) -> Result<Output, AssembleError<Error>> {
+        self.as_query()
+            .render_async(&mut |query: Cow<str>| respond(Arc::from(query)))
+            .await
+    }
+}
@@ -1,11 +1,15 @@
-use super::{ComponentParserInput, ParserConfig, Segment};
-use crate::{IntoSkipOrFatal, Parse};
+use super::{comment, Comment, ComponentParserInput, Escaped, ParserConfig, Segment};
+use crate::{GetSpan, IntoSkipOrFatal, Parse, ParseInput, Recover, Span};
+use alloc::borrow::Cow;
 use derive_more::{Display, Error};
 use split_char_from_str::SplitCharFromStr;
 
+#[cfg(feature = "std")]
+use crate::IntoOwned;
+
 /// Parse a template string whose queries are placed between an opening bracket character and a closing bracket character,
 /// (such as [curly braces](crate::simple_curly_braces())).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct EnclosedTemplateParser<EscapeParser, QueryParser> {
     pub config: ParserConfig,
     pub escape_parser: EscapeParser,
@@ -99,35 +103,71 @@ impl Parser<(), ()> {
 }
 
 /// Error type of [`Parse`] on [`EnclosedTemplateParser`].
-#[derive(Debug, Display, Error, Clone, Copy)]
+#[derive(Debug, Display, Error, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParseError<ParseEscapeError, ParseQueryError> {
     #[display("Unexpected token {_0:?}")]
-    UnexpectedChar(#[error(not(source))] char),
+    UnexpectedToken(Cow<'static, str>, Span),
     #[display("Unexpected end of input")]
-    UnexpectedEndOfInput,
+    UnexpectedEndOfInput(#[error(not(source))] Span),
     #[display("Failed to escape: {_0}")]
     ParseEscape(ParseEscapeError),
     #[display("Failed to parse query: {_0}")]
     ParseQuery(ParseQueryError),
 }
 
+impl<ParseEscapeError, ParseQueryError> GetSpan for ParseError<ParseEscapeError, ParseQueryError>
+where
+    ParseEscapeError: GetSpan,
+    ParseQueryError: GetSpan,
+{
+    fn span(&self) -> Span {
+        match self {
+            ParseError::UnexpectedToken(_, span) | ParseError::UnexpectedEndOfInput(span) => *span,
+            ParseError::ParseEscape(error) => error.span(),
+            ParseError::ParseQuery(error) => error.span(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<ParseEscapeError, ParseQueryError> IntoOwned for ParseError<ParseEscapeError, ParseQueryError>
+where
+    ParseEscapeError: IntoOwned,
+    ParseQueryError: IntoOwned,
+{
+    type Owned = ParseError<ParseEscapeError::Owned, ParseQueryError::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            ParseError::UnexpectedToken(token, span) => ParseError::UnexpectedToken(token, span),
+            ParseError::UnexpectedEndOfInput(span) => ParseError::UnexpectedEndOfInput(span),
+            ParseError::ParseEscape(error) => ParseError::ParseEscape(error.into_owned()),
+            ParseError::ParseQuery(error) => ParseError::ParseQuery(error.into_owned()),
+        }
+    }
+}
+
 impl<'a, EscapeParser, QueryParser> Parse<'a> for Parser<EscapeParser, QueryParser>
 where
-    EscapeParser: Parse<'a, ComponentParserInput<'a>, Output = char>,
+    EscapeParser: Parse<'a, ComponentParserInput<'a>>,
+    EscapeParser::Output: Into<Escaped<'a>>,
     EscapeParser::Error: IntoSkipOrFatal,
     QueryParser: Parse<'a, ComponentParserInput<'a>>,
     QueryParser::Error: IntoSkipOrFatal,
 {
-    type Output = Segment<QueryParser::Output>;
+    type Output = Segment<QueryParser::Output, &'a str>;
     type Error = ParseError<
         <EscapeParser::Error as IntoSkipOrFatal>::Fatal,
         <QueryParser::Error as IntoSkipOrFatal>::Fatal,
     >;
 
-    fn parse(&self, input: &'a str) -> Result<(Self::Output, &'a str), Self::Error> {
+    fn parse(&'a self, input: ParseInput<'a>) -> Result<(Self::Output, &'a str), Self::Error> {
+        let input = self.skip_comments(input)?;
+
         let component_parser_input = ComponentParserInput {
-            text: input,
-            config: self.config,
+            text: input.text,
+            position: input.position,
+            config: &self.config,
         };
 
         let escape_pair = self
@@ -135,7 +175,12 @@ where
             .parse_as_component(component_parser_input)
             .map_err(ParseError::ParseEscape)?;
         if let Some((escaped, rest)) = escape_pair {
-            return Ok((Segment::Character(escaped), rest));
+            let span = input.span_to(rest);
+            let segment = match escaped.into() {
+                Escaped::Character(char) => Segment::Character(char, span),
+                Escaped::Text(text) => Segment::Text(text, span),
+            };
+            return Ok((segment, rest));
         }
 
         let query_pair = self
@@ -143,17 +188,143 @@ where
             .parse_as_component(component_parser_input)
             .map_err(ParseError::ParseQuery)?;
         if let Some((query, rest)) = query_pair {
-            return Ok((Segment::Expression(query), rest));
+            return Ok((Segment::Expression(query, input.span_to(rest)), rest));
+        }
+
+        if let Some(tail) = input.text.strip_prefix(self.config.close_bracket()) {
+            let close_bracket = self.config.close_bracket.clone();
+            return Err(ParseError::UnexpectedToken(
+                close_bracket,
+                input.span_to(tail),
+            ));
+        }
+
+        let end_of_input = || ParseError::UnexpectedEndOfInput(Span::empty(input.position));
+        let (_, tail) = input.text.split_first_char().ok_or_else(end_of_input)?;
+
+        let rest = self.skip_text(ComponentParserInput {
+            text: tail,
+            position: input.span_to(tail).end,
+            config: &self.config,
+        });
+        let text = &input.text[..(input.text.len() - rest.len())];
+        Ok((Segment::Text(text, input.span_to(rest)), rest))
+    }
+
+    fn skip_ignored(&'a self, input: ParseInput<'a>) -> &'a str {
+        // unclosed comments are left to `parse` to report
+        self.skip_comments(input)
+            .map_or(input.text, |input| input.text)
+    }
+}
+
+impl<'a, EscapeParser, QueryParser> Recover<'a> for Parser<EscapeParser, QueryParser>
+where
+    EscapeParser: Parse<'a, ComponentParserInput<'a>>,
+    EscapeParser::Output: Into<Escaped<'a>>,
+    EscapeParser::Error: IntoSkipOrFatal,
+    QueryParser: Parse<'a, ComponentParserInput<'a>>,
+    QueryParser::Error: IntoSkipOrFatal,
+    Self::Error: GetSpan,
+{
+    /// Skip a malformed query up to its closing bracket, or skip the text covered by the error.
+    fn recover(&self, input: ParseInput<'a>, error: &Self::Error) -> &'a str {
+        let (open_bracket, close_bracket) =
+            (self.config.open_bracket(), self.config.close_bracket());
+
+        if let Some(tail) = input.text.strip_prefix(open_bracket) {
+            return match tail.find(close_bracket) {
+                Some(index) => &tail[(index + close_bracket.len())..],
+                None => "",
+            };
+        }
+
+        let consumed = error
+            .span()
+            .end
+            .offset
+            .saturating_sub(input.position.offset);
+        input.text.get(consumed..).unwrap_or_default()
+    }
+}
+
+impl<'a, EscapeParser, QueryParser> Parser<EscapeParser, QueryParser>
+where
+    EscapeParser: Parse<'a, ComponentParserInput<'a>>,
+    EscapeParser::Output: Into<Escaped<'a>>,
+    EscapeParser::Error: IntoSkipOrFatal,
+    QueryParser: Parse<'a, ComponentParserInput<'a>>,
+    QueryParser::Error: IntoSkipOrFatal,
+{
+    /// Skip all the literal characters until the start of the next escape, query, or unexpected token.
+    fn skip_text(&'a self, mut input: ComponentParserInput<'a>) -> &'a str {
+        while let Some((_, tail)) = input.text.split_first_char() {
+            if input.text.starts_with(self.config.close_bracket())
+                || self.starts_with_comment(input.text)
+                || !matches!(self.escape_parser.parse_as_component(input), Ok(None))
+                || !matches!(self.query_parser.parse_as_component(input), Ok(None))
+            {
+                break;
+            }
+            input.position = input.span_to(tail).end;
+            input.text = tail;
         }
+        input.text
+    }
 
-        let (head, tail) = input
-            .split_first_char()
-            .ok_or(ParseError::UnexpectedEndOfInput)?;
+    /// Check whether `text` starts with a comment, such as `{# note #}`.
+    fn starts_with_comment(&self, text: &str) -> bool {
+        self.config
+            .comment()
+            .is_some_and(|comment| text.starts_with(comment.open_bracket()))
+    }
 
-        if head == self.config.close_bracket {
-            return Err(ParseError::UnexpectedChar(head));
+    /// Skip the comments at the start of `input`.
+    fn skip_comments(
+        &'a self,
+        mut input: ParseInput<'a>,
+    ) -> Result<ParseInput<'a>, <Self as Parse<'a>>::Error> {
+        while let Some((_, rest)) = self
+            .config
+            .parse_comment(input)
+            .map_err(ParseError::UnexpectedEndOfInput)?
+        {
+            input = ParseInput {
+                text: rest,
+                position: input.span_to(rest).end,
+            };
         }
+        Ok(input)
+    }
 
-        Ok((Segment::Character(head), tail))
+    /// List the comments of `text`, in order, up to the first parse error.
+    ///
+    /// Comments produce no segment, so this is how tooling can see them:
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "std"))] fn main() {}
+    /// # #[cfg(feature = "std")] fn main() {
+    /// # use pretty_assertions::assert_eq;
+    /// use lazy_template::enclosed::{CommentConfig, Parser, ParserConfig, SimpleEscapeParser, SimpleQueryParser};
+    /// let parser = Parser::curly_braces()
+    ///     .with_config(ParserConfig::curly_braces().with_comment(CommentConfig::hash_curly_braces()))
+    ///     .with_escape_parser(SimpleEscapeParser)
+    ///     .with_query_parser(SimpleQueryParser);
+    /// let comments: Vec<_> = parser
+    ///     .comments("{# greeting #}Hello, {name}!\n{#TODO: sign #}")
+    ///     .map(|comment| (comment.text, comment.span.to_string()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     comments,
+    ///     [(" greeting ", "1:1".to_string()), ("TODO: sign ", "2:1".to_string())],
+    /// );
+    /// # }
+    /// ```
+    pub fn comments(&'a self, text: &'a str) -> impl Iterator<Item = Comment<'a>> + 'a {
+        comment::comments(text, |input| match self.config.parse_comment(input) {
+            Ok(Some((comment, rest))) => Some((Some(comment), rest)),
+            Ok(None) => self.parse(input).ok().map(|(_, rest)| (None, rest)),
+            Err(_) => None,
+        })
     }
 }
@@ -0,0 +1,198 @@
+use super::{EnclosedTemplateParser, ParserConfig};
+use crate::{GetQuery, GetSpan, IntoOwned, Parse, ParseInput, Span};
+use derive_more::{Display, Error};
+use pipe_trait::Pipe;
+
+/// Parse templates with partials, such as `{>header}`, which include other templates when rendered by
+/// [`Partials`](crate::Partials).
+///
+/// Partials are parsed with the delimiters of the inner parser, and the other segments with the inner parser itself.
+/// Without this parser, `{>header}` is parsed by the query parser like any other query:
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{
+///     enclosed::{partial::PartialSegment, Parser, PartialParser, SimpleEscapeParser, SimpleQueryParser},
+///     IntoTemplateSystem,
+/// };
+/// let parser = Parser::curly_braces()
+///     .with_escape_parser(SimpleEscapeParser)
+///     .with_query_parser(SimpleQueryParser);
+/// let system = PartialParser::new(parser).into_template_system::<&str>();
+/// let parsed_template = system.eager_parse::<Vec<_>>("{>header}Hello, {name}!").unwrap();
+/// let partials: Vec<_> = parsed_template
+///     .segments()
+///     .filter_map(|segment| match *segment {
+///         PartialSegment::Partial(name, _) => Some(name),
+///         PartialSegment::Segment(_) => None,
+///     })
+///     .collect();
+/// assert_eq!(partials, ["header"]);
+/// ```
+///
+/// The parsed segments cannot be [rendered](crate::Render) on their own, only by [`Partials`](crate::Partials):
+///
+/// ```compile_fail
+/// use lazy_template::{
+///     enclosed::{Parser, PartialParser, SimpleEscapeParser, SimpleQueryParser},
+///     IntoTemplateSystem,
+/// };
+/// let parser = Parser::curly_braces()
+///     .with_escape_parser(SimpleEscapeParser)
+///     .with_query_parser(SimpleQueryParser);
+/// let output = PartialParser::new(parser)
+///     .into_template_system::<&str>()
+///     .lazy_parse("{>header}Hello, {name}!")
+///     .to_string(|query| Ok::<_, String>(query));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PartialParser<Inner> {
+    pub config: ParserConfig,
+    pub inner: Inner,
+}
+pub type Parser<Inner> = PartialParser<Inner>;
+
+impl<EscapeParser, QueryParser> Parser<EnclosedTemplateParser<EscapeParser, QueryParser>> {
+    /// Parse the partials with the delimiters of `inner`, and the other segments with `inner` itself.
+    pub fn new(inner: EnclosedTemplateParser<EscapeParser, QueryParser>) -> Self {
+        Parser {
+            config: inner.config.clone(),
+            inner,
+        }
+    }
+}
+
+pub type ParseOutput<'a, Segment> = PartialSegment<Segment, &'a str>;
+
+/// Segment of a template with partials.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PartialSegment<Segment, Text> {
+    /// A segment of the inner parser.
+    Segment(Segment),
+    /// The name of another template to include, such as `{>header}`.
+    Partial(Text, Span),
+}
+
+impl<Segment, Text> GetSpan for PartialSegment<Segment, Text>
+where
+    Segment: GetSpan,
+{
+    fn span(&self) -> Span {
+        match self {
+            PartialSegment::Segment(segment) => segment.span(),
+            PartialSegment::Partial(_, span) => *span,
+        }
+    }
+}
+
+impl<Segment, Text> GetQuery for PartialSegment<Segment, Text>
+where
+    Segment: GetQuery,
+{
+    type Query = Segment::Query;
+    fn query(&self) -> Option<Self::Query> {
+        match self {
+            PartialSegment::Segment(segment) => segment.query(),
+            PartialSegment::Partial(..) => None,
+        }
+    }
+}
+
+impl<Segment, Text> IntoOwned for PartialSegment<Segment, Text>
+where
+    Segment: IntoOwned,
+    Text: IntoOwned,
+{
+    type Owned = PartialSegment<Segment::Owned, Text::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            PartialSegment::Segment(segment) => PartialSegment::Segment(segment.into_owned()),
+            PartialSegment::Partial(name, span) => PartialSegment::Partial(name.into_owned(), span),
+        }
+    }
+}
+
+/// Error type of [`Parse`] on [`PartialParser`].
+#[derive(Debug, Display, Error, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseError<SegmentError> {
+    #[display("{_0}")]
+    Segment(SegmentError),
+    #[display("Unexpected end of input")]
+    UnexpectedEndOfInput(#[error(not(source))] Span),
+    #[display("Partial has no name")]
+    MissingName(#[error(not(source))] Span),
+}
+
+impl<SegmentError> GetSpan for ParseError<SegmentError>
+where
+    SegmentError: GetSpan,
+{
+    fn span(&self) -> Span {
+        match self {
+            ParseError::Segment(error) => error.span(),
+            ParseError::UnexpectedEndOfInput(span) | ParseError::MissingName(span) => *span,
+        }
+    }
+}
+
+impl<'a, Inner> Parse<'a> for Parser<Inner>
+where
+    Inner: Parse<'a>,
+{
+    type Output = ParseOutput<'a, Inner::Output>;
+    type Error = ParseError<Inner::Error>;
+
+    fn parse(&'a self, input: ParseInput<'a>) -> Result<(Self::Output, &'a str), Self::Error> {
+        let after_ignored = self.inner.skip_ignored(input);
+        let after_ignored = ParseInput {
+            text: after_ignored,
+            position: input.span_to(after_ignored).end,
+        };
+        let Some((name, rest)) = self.parse_partial(after_ignored)? else {
+            // the inner parser skips the same comments
+            return self
+                .inner
+                .parse(input)
+                .map(|(segment, rest)| (PartialSegment::Segment(segment), rest))
+                .map_err(ParseError::Segment);
+        };
+        Ok((
+            PartialSegment::Partial(name, after_ignored.span_to(rest)),
+            rest,
+        ))
+    }
+
+    fn skip_ignored(&'a self, input: ParseInput<'a>) -> &'a str {
+        self.inner.skip_ignored(input)
+    }
+}
+
+impl<Inner> Parser<Inner> {
+    /// Parse a partial at the start of `input`, if there is one, and return its name and the remaining text.
+    fn parse_partial<'a, SegmentError>(
+        &self,
+        input: ParseInput<'a>,
+    ) -> Result<Option<(&'a str, &'a str)>, ParseError<SegmentError>> {
+        let (open_bracket, close_bracket) =
+            (self.config.open_bracket(), self.config.close_bracket());
+        let Some(tail) = input
+            .text
+            .strip_prefix(open_bracket)
+            .and_then(|tail| tail.strip_prefix('>'))
+        else {
+            return Ok(None);
+        };
+        let Some(close_index) = tail.find(close_bracket) else {
+            let end = input.span_to("").end;
+            return ParseError::UnexpectedEndOfInput(Span::empty(end)).pipe(Err);
+        };
+        let name = tail[..close_index].trim();
+        let rest = &tail[(close_index + close_bracket.len())..];
+        if name.is_empty() {
+            return ParseError::MissingName(input.span_to(rest)).pipe(Err);
+        }
+        Ok(Some((name, rest)))
+    }
+}
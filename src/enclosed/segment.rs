@@ -1,32 +1,147 @@
-use crate::Render;
+use crate::{AsyncRender, GetQuery, GetSpan, Render, Span};
+use core::future::Future;
 use derive_more::Display;
 use pipe_trait::Pipe;
 
+#[cfg(feature = "std")]
+use crate::IntoOwned;
+
 /// Represent a segment of a parsed template.
 #[derive(Debug, Clone, Copy)]
-pub enum Segment<Query> {
-    Character(char),
-    Expression(Query),
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Segment<Query, Text> {
+    /// A single character, usually produced by an escape sequence.
+    Character(char, Span),
+    /// A run of literal text.
+    Text(Text, Span),
+    /// A query to be sent to the responder.
+    Expression(Query, Span),
+}
+
+impl<Query, Text> GetSpan for Segment<Query, Text> {
+    fn span(&self) -> Span {
+        match self {
+            Segment::Character(_, span) | Segment::Text(_, span) | Segment::Expression(_, span) => {
+                *span
+            }
+        }
+    }
+}
+
+impl<Query, Text> GetQuery for Segment<Query, Text>
+where
+    Query: Clone,
+{
+    type Query = Query;
+    fn query(&self) -> Option<Query> {
+        match self {
+            Segment::Expression(query, _) => Some(query.clone()),
+            Segment::Character(..) | Segment::Text(..) => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Query, Text> IntoOwned for Segment<Query, Text>
+where
+    Query: IntoOwned,
+    Text: IntoOwned,
+{
+    type Owned = Segment<Query::Owned, Text::Owned>;
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Segment::Character(char, span) => Segment::Character(char, span),
+            Segment::Text(text, span) => Segment::Text(text.into_owned(), span),
+            Segment::Expression(query, span) => Segment::Expression(query.into_owned(), span),
+        }
+    }
 }
 
 /// Returned upon the [rendering](Render) of a [`Segment`].
 ///
 /// Value of this type can be converted to a string by using the [`Display`] trait.
 #[derive(Debug, Display, Clone, Copy)]
-pub enum SegmentDisplay<Output> {
+pub enum SegmentDisplay<Output, Text> {
     Character(char),
+    Text(Text),
     ExpressionResult(Output),
 }
 
-impl<Respond, Output, Error, Query> Render<Respond, SegmentDisplay<Output>, Error>
-    for Segment<Query>
+/// Returned upon the [rendering](Render) of a [`Segment`] whose query renders itself, such as a
+/// [`FormattedQuery`](super::FormattedQuery) or a [`NestedQuery`](super::NestedQuery).
+///
+/// The respond function of such segment receives what the query asks for instead of the query itself.
+#[derive(Debug, Display, Clone, Copy)]
+pub struct RenderedSegmentDisplay<Output, Text>(pub SegmentDisplay<Output, Text>);
+
+impl<Respond, Output, Error, Query, Text> Render<Respond, SegmentDisplay<Output, Text>, Error>
+    for Segment<Query, Text>
 where
     Respond: FnMut(Query) -> Result<Output, Error>,
 {
-    fn render(self, respond: &mut Respond) -> Result<SegmentDisplay<Output>, Error> {
+    fn render(self, respond: &mut Respond) -> Result<SegmentDisplay<Output, Text>, Error> {
         Ok(match self {
-            Segment::Character(value) => SegmentDisplay::Character(value),
-            Segment::Expression(query) => respond(query)?.pipe(SegmentDisplay::ExpressionResult),
+            Segment::Character(value, _) => SegmentDisplay::Character(value),
+            Segment::Text(text, _) => SegmentDisplay::Text(text),
+            Segment::Expression(query, _) => respond(query)?.pipe(SegmentDisplay::ExpressionResult),
         })
     }
 }
+
+impl<Respond, Output, Error, Query, Text>
+    Render<Respond, RenderedSegmentDisplay<Output, Text>, Error> for Segment<Query, Text>
+where
+    Query: Render<Respond, Output, Error>,
+{
+    fn render(self, respond: &mut Respond) -> Result<RenderedSegmentDisplay<Output, Text>, Error> {
+        Ok(match self {
+            Segment::Character(value, _) => SegmentDisplay::Character(value),
+            Segment::Text(text, _) => SegmentDisplay::Text(text),
+            Segment::Expression(query, _) => query
+                .render(respond)?
+                .pipe(SegmentDisplay::ExpressionResult),
+        }
+        .pipe(RenderedSegmentDisplay))
+    }
+}
+
+impl<Respond, QueryFuture, Output, Error, Query, Text>
+    AsyncRender<Respond, SegmentDisplay<Output, Text>, Error> for Segment<Query, Text>
+where
+    Respond: FnMut(Query) -> QueryFuture,
+    QueryFuture: Future<Output = Result<Output, Error>>,
+{
+    async fn render_async(
+        self,
+        respond: &mut Respond,
+    ) -> Result<SegmentDisplay<Output, Text>, Error> {
+        Ok(match self {
+            Segment::Character(value, _) => SegmentDisplay::Character(value),
+            Segment::Text(text, _) => SegmentDisplay::Text(text),
+            Segment::Expression(query, _) => {
+                respond(query).await?.pipe(SegmentDisplay::ExpressionResult)
+            }
+        })
+    }
+}
+
+impl<Respond, Output, Error, Query, Text>
+    AsyncRender<Respond, RenderedSegmentDisplay<Output, Text>, Error> for Segment<Query, Text>
+where
+    Query: AsyncRender<Respond, Output, Error>,
+{
+    async fn render_async(
+        self,
+        respond: &mut Respond,
+    ) -> Result<RenderedSegmentDisplay<Output, Text>, Error> {
+        Ok(match self {
+            Segment::Character(value, _) => SegmentDisplay::Character(value),
+            Segment::Text(text, _) => SegmentDisplay::Text(text),
+            Segment::Expression(query, _) => query
+                .render_async(respond)
+                .await?
+                .pipe(SegmentDisplay::ExpressionResult),
+        }
+        .pipe(RenderedSegmentDisplay))
+    }
+}
@@ -1,27 +1,49 @@
-use super::{ComponentParserInput, ParserConfig};
-use crate::Parse;
+use super::{ComponentParserInput, Escaped, ParserConfig};
+use crate::{GetSpan, Parse, Span};
 use derive_more::{Display, Error};
 use split_char_from_str::SplitCharFromStr;
 
+#[cfg(feature = "std")]
+use crate::IntoOwned;
+
 pub type ParserInput<'a> = ComponentParserInput<'a>;
 
 #[derive(Debug, Clone, Copy)]
 pub struct SimpleEscapeParser;
 pub type Parser = SimpleEscapeParser;
 
-pub type SimpleEscape = char;
-pub type ParseOutput = SimpleEscape;
+pub type SimpleEscape<'a> = Escaped<'a>;
+pub type ParseOutput<'a> = SimpleEscape<'a>;
 
 #[derive(Debug, Display, Error, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParseError {
     #[display("Unsupported escape code {_0:?}")]
-    UnsupportedEscapeCode(#[error(not(source))] char),
+    UnsupportedEscapeCode(char, Span),
     #[display("Unexpected end of input")]
-    UnexpectedEndOfInput,
+    UnexpectedEndOfInput(#[error(not(source))] Span),
+}
+
+impl GetSpan for ParseError {
+    fn span(&self) -> Span {
+        match self {
+            ParseError::UnsupportedEscapeCode(_, span) | ParseError::UnexpectedEndOfInput(span) => {
+                *span
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl IntoOwned for ParseError {
+    type Owned = Self;
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
 }
 
 impl<'a> Parse<'a, ParserInput<'a>> for Parser {
-    type Output = ParseOutput;
+    type Output = ParseOutput<'a>;
     type Error = Option<ParseError>;
 
     fn parse(&self, input: ParserInput<'a>) -> Result<(Self::Output, &'a str), Self::Error> {
@@ -31,23 +53,37 @@ impl<'a> Parse<'a, ParserInput<'a>> for Parser {
             return Err(None);
         }
 
+        let escape_code_position = input.span_to(tail).end;
+
+        if let Some(length) = escape_delimiter(tail, input.config) {
+            return Ok((Escaped::Text(&tail[..length]), &tail[length..]));
+        }
+
         let (escape_code, rest) = tail
             .split_first_char()
-            .ok_or(ParseError::UnexpectedEndOfInput)
+            .ok_or(ParseError::UnexpectedEndOfInput(Span::empty(
+                escape_code_position,
+            )))
             .map_err(Some)?;
 
-        let char = escape_bracket(escape_code, input.config)
-            .or_else(|| make_special_character(escape_code))
-            .ok_or(ParseError::UnsupportedEscapeCode(escape_code))
+        let char = make_special_character(escape_code)
+            .ok_or_else(|| {
+                let span = Span::covering(escape_code_position, &tail[..escape_code.len_utf8()]);
+                ParseError::UnsupportedEscapeCode(escape_code, span)
+            })
             .map_err(Some)?;
 
-        Ok((char, rest))
+        Ok((Escaped::Character(char), rest))
     }
 }
 
-fn escape_bracket(escape_code: char, config: ParserConfig) -> Option<char> {
-    (escape_code == config.open_bracket || escape_code == config.close_bracket)
-        .then_some(escape_code)
+/// Find the length of the longest delimiter at the start of `text`.
+fn escape_delimiter(text: &str, config: &ParserConfig) -> Option<usize> {
+    [config.open_bracket(), config.close_bracket()]
+        .into_iter()
+        .filter(|delimiter| text.starts_with(delimiter))
+        .map(str::len)
+        .max()
 }
 
 fn make_special_character(escape_code: char) -> Option<char> {
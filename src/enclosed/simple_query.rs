@@ -1,8 +1,12 @@
 use super::ComponentParserInput;
-use crate::Parse;
+use crate::{GetSpan, Parse, Span};
+use alloc::borrow::Cow;
 use derive_more::{Display, Error};
-use pipe_trait::Pipe;
-use split_char_from_str::SplitCharFromStr;
+
+#[cfg(feature = "std")]
+use crate::IntoOwned;
+#[cfg(feature = "std")]
+use std::sync::Arc;
 
 pub type ParserInput<'a> = ComponentParserInput<'a>;
 
@@ -13,12 +17,29 @@ pub type Parser = SimpleQueryParser;
 pub type SimpleQuery<'a> = &'a str;
 pub type ParseOutput<'a> = SimpleQuery<'a>;
 
-#[derive(Debug, Display, Error, Clone, Copy)]
+#[derive(Debug, Display, Error, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParseError {
     #[display("Unexpected token {_0:?}")]
-    UnexpectedChar(#[error(not(source))] char),
+    UnexpectedToken(Cow<'static, str>, Span),
     #[display("Unexpected end of input")]
-    UnexpectedEndOfInput,
+    UnexpectedEndOfInput(#[error(not(source))] Span),
+}
+
+impl GetSpan for ParseError {
+    fn span(&self) -> Span {
+        match self {
+            ParseError::UnexpectedToken(_, span) | ParseError::UnexpectedEndOfInput(span) => *span,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl IntoOwned for ParseError {
+    type Owned = Self;
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
 }
 
 impl<'a> Parse<'a, ParserInput<'a>> for Parser {
@@ -26,23 +47,20 @@ impl<'a> Parse<'a, ParserInput<'a>> for Parser {
     type Error = Option<ParseError>;
 
     fn parse(&self, input: ParserInput<'a>) -> Result<(Self::Output, &'a str), Self::Error> {
-        let (head, tail) = input.text.split_first_char().ok_or(None)?;
-
-        if head == input.config.close_bracket {
-            return head.pipe(ParseError::UnexpectedChar).pipe(Some).pipe(Err);
-        }
+        let close_bracket = input.config.close_bracket();
 
-        if head != input.config.open_bracket {
-            return Err(None);
-        }
+        let tail = input.strip_open_bracket(ParseError::UnexpectedToken)?;
 
-        let (close_index, _) = tail
-            .char_indices()
-            .find(|(_, char)| *char == input.config.close_bracket)
-            .ok_or(ParseError::UnexpectedEndOfInput)
+        let close_index = tail
+            .find(close_bracket)
+            .ok_or_else(|| ParseError::UnexpectedEndOfInput(Span::empty(input.span_to("").end)))
             .map_err(Some)?;
         let query = &tail[..close_index];
-        let rest = &tail[(close_index + 1)..];
+        let rest = &tail[(close_index + close_bracket.len())..];
         Ok((query, rest))
     }
 }
+
+/// Owned counterpart of [`SimpleQuery`], see [`IntoOwned`].
+#[cfg(feature = "std")]
+pub type OwnedSimpleQuery = Arc<str>;
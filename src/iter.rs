@@ -1,5 +1,8 @@
+mod concurrent;
 mod eager_parse_iter;
 mod lazy_parse_iter;
+mod recovering_parse_iter;
 
 pub use eager_parse_iter::*;
 pub use lazy_parse_iter::*;
+pub use recovering_parse_iter::*;
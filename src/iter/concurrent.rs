@@ -0,0 +1,171 @@
+#![cfg(feature = "std")]
+use super::ParsedTemplate;
+use crate::{AsyncRender, GetQuery, Render, TemplateApplicationError};
+use core::{
+    convert::Infallible,
+    fmt::{self, Write},
+    future::{poll_fn, Future},
+    ops::Deref,
+    pin::Pin,
+    task::Poll,
+};
+use std::{panic, thread};
+
+type ConcurrentResult<RenderError> =
+    Result<String, TemplateApplicationError<Infallible, RenderError, fmt::Error>>;
+
+impl<'a, SegmentContainer, Query> ParsedTemplate<SegmentContainer, Query>
+where
+    SegmentContainer: Deref + 'a,
+    &'a SegmentContainer::Target: IntoIterator,
+{
+    /// Send all the queries of the template to `respond` at once, each from its own thread, then join the resulting
+    /// segment outputs together into a [`String`] in the order of the template.
+    ///
+    /// This is useful when the queries are slow to answer (e.g. network requests) and independent of each other:
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// let system = lazy_template::simple_curly_braces();
+    /// let parsed_template = system.eager_parse::<Vec<_>>("{a}, {b}, {c}").unwrap();
+    /// let output = parsed_template
+    ///     .to_string_concurrent(|query| {
+    ///         std::thread::sleep(std::time::Duration::from_millis(10)); // slow lookup
+    ///         Ok::<_, String>(query.to_uppercase())
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(output, "A, B, C");
+    /// ```
+    ///
+    /// If some queries fail, the error of the first failing segment is returned.
+    pub fn to_string_concurrent<
+        Segment,
+        RenderOutput,
+        RenderError,
+        QueryOutput,
+        QueryError,
+        Respond,
+    >(
+        &'a self,
+        respond: Respond,
+    ) -> ConcurrentResult<RenderError>
+    where
+        <&'a SegmentContainer::Target as IntoIterator>::Item: Deref<Target = Segment>,
+        Segment: GetQuery + Clone + Sync + 'a,
+        for<'r> Segment: Render<&'r Respond, RenderOutput, RenderError>,
+        RenderOutput: fmt::Display + Send,
+        RenderError: Send,
+        Respond: Fn(Query) -> Result<QueryOutput, QueryError> + Sync,
+    {
+        let segments: Vec<Segment> = self.segments().map(|segment| segment.clone()).collect();
+        let render = |segment: &Segment| segment.clone().render(&mut &respond);
+
+        let results = thread::scope(|scope| {
+            // every expression is spawned before any is joined, so that all of their queries are sent at once
+            let workers: Vec<_> = segments
+                .iter()
+                .map(|segment| {
+                    segment
+                        .query()
+                        .is_some()
+                        .then(|| scope.spawn(move || render(segment)))
+                })
+                .collect();
+            segments
+                .iter()
+                .zip(workers)
+                .map(|(segment, worker)| match worker {
+                    Some(worker) => worker
+                        .join()
+                        .unwrap_or_else(|panic| panic::resume_unwind(panic)),
+                    None => render(segment),
+                })
+                .collect::<Vec<_>>()
+        });
+
+        join_results(results)
+    }
+
+    /// Send all the queries of the template to a respond function that returns [futures](Future), await them together,
+    /// then join the resulting segment outputs together into a [`String`] in the order of the template.
+    ///
+    /// The futures are polled concurrently within the returned future, so any executor can be used.
+    ///
+    /// If some queries fail, the error of the first failing segment is returned.
+    pub async fn to_string_concurrent_async<
+        Segment,
+        RenderOutput,
+        RenderError,
+        QueryOutput,
+        QueryError,
+        QueryFuture,
+        Respond,
+    >(
+        &'a self,
+        respond: Respond,
+    ) -> ConcurrentResult<RenderError>
+    where
+        <&'a SegmentContainer::Target as IntoIterator>::Item: Deref<Target = Segment>,
+        Segment: Clone + 'a,
+        for<'r> Segment: AsyncRender<&'r Respond, RenderOutput, RenderError>,
+        RenderOutput: fmt::Display,
+        Respond: Fn(Query) -> QueryFuture,
+        QueryFuture: Future<Output = Result<QueryOutput, QueryError>>,
+    {
+        let respond = &respond;
+        let mut futures: Vec<_> = self
+            .segments()
+            .map(|segment| {
+                let segment = segment.clone();
+                let future = async move {
+                    let mut respond = respond;
+                    segment.render_async(&mut respond).await
+                };
+                Some(Box::pin(future))
+            })
+            .collect();
+
+        let mut results: Vec<_> = futures.iter().map(|_| None).collect();
+        poll_fn(|context| {
+            let mut pending = false;
+            for (slot, result) in futures.iter_mut().zip(&mut results) {
+                let Some(future) = slot else {
+                    continue;
+                };
+                match Pin::as_mut(future).poll(context) {
+                    Poll::Ready(output) => {
+                        *result = Some(output);
+                        *slot = None;
+                    }
+                    Poll::Pending => pending = true,
+                }
+            }
+            match pending {
+                true => Poll::Pending,
+                false => Poll::Ready(()),
+            }
+        })
+        .await;
+
+        join_results(
+            results
+                .into_iter()
+                .map(|result| result.expect("every segment should have been rendered")),
+        )
+    }
+}
+
+/// Join the outputs of the segments in order, or return the error of the first failing segment.
+fn join_results<RenderOutput, RenderError>(
+    results: impl IntoIterator<Item = Result<RenderOutput, RenderError>>,
+) -> ConcurrentResult<RenderError>
+where
+    RenderOutput: fmt::Display,
+{
+    let mut buf = String::new();
+    for result in results {
+        let output = result.map_err(TemplateApplicationError::Query)?;
+        write!(buf, "{output}").map_err(TemplateApplicationError::Write)?;
+    }
+    Ok(buf)
+}
@@ -1,6 +1,13 @@
-use crate::Template;
+use crate::{GetQuery, Template};
 use core::{convert::Infallible, marker::PhantomData, ops::Deref};
+
+#[cfg(feature = "std")]
+use crate::IntoOwned;
+#[cfg(feature = "std")]
+use core::hash::Hash;
 use derive_more::IntoIterator;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
 
 #[derive(Debug, Clone, Copy)]
 pub struct EagerParseIter<SegmentIter>(SegmentIter);
@@ -9,12 +16,12 @@ impl<SegmentIter> Iterator for EagerParseIter<SegmentIter>
 where
     SegmentIter: Iterator,
     SegmentIter::Item: Deref,
-    <SegmentIter::Item as Deref>::Target: Copy,
+    <SegmentIter::Item as Deref>::Target: Clone,
 {
     type Item = Result<<SegmentIter::Item as Deref>::Target, Infallible>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.next().as_deref().copied().map(Ok)
+        self.0.next().as_deref().cloned().map(Ok)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -23,14 +30,20 @@ where
 }
 
 #[derive(Debug, Clone, Copy, IntoIterator)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct ParsedTemplate<SegmentContainer, Query> {
     #[into_iterator]
     container: SegmentContainer,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _query: PhantomData<Query>, // phantom Query is necessary to enable type inference later on
 }
 
 impl<SegmentContainer, Query> ParsedTemplate<SegmentContainer, Query> {
-    pub(crate) fn new(container: SegmentContainer) -> Self {
+    /// Create a parsed template from segments that were parsed ahead of time.
+    ///
+    /// This is used by the code generated by the `lazy_template!` macro of the `lazy-template-macros` crate.
+    pub const fn new(container: SegmentContainer) -> Self {
         Self {
             container,
             _query: PhantomData,
@@ -38,6 +51,51 @@ impl<SegmentContainer, Query> ParsedTemplate<SegmentContainer, Query> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<SegmentContainer, Query> ParsedTemplate<SegmentContainer, Query>
+where
+    SegmentContainer: IntoIterator,
+    SegmentContainer::Item: IntoOwned,
+    Query: IntoOwned,
+{
+    /// Convert the segments into ones that own their data, so that the parsed template no longer borrows the template
+    /// string.
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// use lazy_template::{enclosed::OwnedSimpleQuery, simple_curly_braces};
+    /// use std::sync::Arc;
+    /// let system = simple_curly_braces();
+    /// let template_string = String::from("Hello, {name}!");
+    /// let parsed_template = system
+    ///     .eager_parse::<Vec<_>>(&template_string)
+    ///     .unwrap()
+    ///     .into_owned::<Arc<[_]>>();
+    /// drop(template_string);
+    /// let output = std::thread::spawn(move || {
+    ///     parsed_template
+    ///         .to_template()
+    ///         .to_string(|query: OwnedSimpleQuery| match query.as_ref() {
+    ///             "name" => Ok("Alice"),
+    ///             _ => Err(format!("Can't answer {query:?}")),
+    ///         })
+    ///         .unwrap()
+    /// })
+    /// .join()
+    /// .unwrap();
+    /// assert_eq!(output, "Hello, Alice!");
+    /// ```
+    pub fn into_owned<OwnedSegmentContainer>(
+        self,
+    ) -> ParsedTemplate<OwnedSegmentContainer, Query::Owned>
+    where
+        OwnedSegmentContainer: FromIterator<<SegmentContainer::Item as IntoOwned>::Owned>,
+    {
+        let container = self.container.into_iter().map(IntoOwned::into_owned);
+        ParsedTemplate::new(container.collect())
+    }
+}
+
 impl<'a, SegmentContainer, Query> ParsedTemplate<SegmentContainer, Query>
 where
     SegmentContainer: Deref + 'a,
@@ -59,4 +117,45 @@ where
     pub fn segments(&'a self) -> <&'a SegmentContainer::Target as IntoIterator>::IntoIter {
         self.container.into_iter()
     }
+
+    /// List the queries of the template without sending them to a responder.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "std"))] fn main() {}
+    /// # #[cfg(feature = "std")] fn main() {
+    /// # use pretty_assertions::assert_eq;
+    /// let system = lazy_template::simple_curly_braces();
+    /// let parsed_template = system.eager_parse::<Vec<_>>("{a}, {b}, {a}").unwrap();
+    /// let queries: Vec<_> = parsed_template.queries().collect();
+    /// assert_eq!(queries, ["a", "b", "a"]);
+    /// # }
+    /// ```
+    pub fn queries<Segment>(&'a self) -> impl Iterator<Item = Segment::Query> + 'a
+    where
+        <&'a SegmentContainer::Target as IntoIterator>::Item: Deref<Target = Segment>,
+        Segment: GetQuery + 'a,
+    {
+        self.segments().filter_map(|segment| segment.query())
+    }
+
+    /// List the distinct queries of the template in the order of their first appearances.
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// let system = lazy_template::simple_curly_braces();
+    /// let parsed_template = system.eager_parse::<Vec<_>>("{a}, {b}, {a}").unwrap();
+    /// assert_eq!(parsed_template.unique_queries(), ["a", "b"]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn unique_queries<Segment>(&'a self) -> Vec<Segment::Query>
+    where
+        <&'a SegmentContainer::Target as IntoIterator>::Item: Deref<Target = Segment>,
+        Segment: GetQuery + 'a,
+        Segment::Query: Eq + Hash + Clone,
+    {
+        let mut seen = HashSet::new();
+        self.queries()
+            .filter(|query| seen.insert(query.clone()))
+            .collect()
+    }
 }
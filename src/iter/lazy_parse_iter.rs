@@ -1,8 +1,9 @@
-use crate::Parse;
+use crate::{Parse, ParseInput, Position};
 
 #[derive(Debug)]
 pub struct LazyParseIter<'a, Parser> {
     template: &'a str,
+    position: Position,
     parser: &'a Parser,
 }
 
@@ -16,7 +17,16 @@ impl<'a, Parser> Copy for LazyParseIter<'a, Parser> {}
 
 impl<'a, Parser> LazyParseIter<'a, Parser> {
     pub(crate) fn new(template: &'a str, parser: &'a Parser) -> Self {
-        Self { template, parser }
+        Self {
+            template,
+            position: Position::START,
+            parser,
+        }
+    }
+
+    /// Position of the next segment to be parsed.
+    pub fn position(&self) -> Position {
+        self.position
     }
 }
 
@@ -27,15 +37,28 @@ where
     type Item = Result<Parser::Output, Parser::Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.template.is_empty() {
+        let input = ParseInput {
+            text: self.template,
+            position: self.position,
+        };
+        let text = self.parser.skip_ignored(input);
+        if text.is_empty() {
+            self.position = input.span_to(text).end;
+            self.template = text;
             return None;
         }
 
-        let (segment, rest) = match self.parser.parse(self.template) {
+        let input = ParseInput {
+            text,
+            position: input.span_to(text).end,
+        };
+
+        let (segment, rest) = match self.parser.parse(input) {
             Ok(pair) => pair,
             Err(error) => return Some(Err(error)),
         };
 
+        self.position = input.span_to(rest).end;
         self.template = rest;
         Some(Ok(segment))
     }
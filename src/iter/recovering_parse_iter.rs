@@ -0,0 +1,81 @@
+use crate::{ParseInput, Position, Recover};
+use split_char_from_str::SplitCharFromStr;
+
+/// Like [`LazyParseIter`](crate::iter::LazyParseIter), but continues parsing after errors.
+///
+/// Every error is yielded in place of the segment that failed to parse, then the [parser](Recover) skips the faulty
+/// part of the template string and parsing resumes.
+#[derive(Debug)]
+pub struct RecoveringParseIter<'a, Parser> {
+    template: &'a str,
+    position: Position,
+    parser: &'a Parser,
+}
+
+impl<'a, Parser> Clone for RecoveringParseIter<'a, Parser> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, Parser> Copy for RecoveringParseIter<'a, Parser> {}
+
+impl<'a, Parser> RecoveringParseIter<'a, Parser> {
+    pub(crate) fn new(template: &'a str, parser: &'a Parser) -> Self {
+        Self {
+            template,
+            position: Position::START,
+            parser,
+        }
+    }
+
+    /// Position of the next segment to be parsed.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+}
+
+impl<'a, Parser> Iterator for RecoveringParseIter<'a, Parser>
+where
+    Parser: Recover<'a>,
+{
+    type Item = Result<Parser::Output, Parser::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = ParseInput {
+            text: self.template,
+            position: self.position,
+        };
+        let text = self.parser.skip_ignored(input);
+        if text.is_empty() {
+            self.position = input.span_to(text).end;
+            self.template = text;
+            return None;
+        }
+
+        let input = ParseInput {
+            text,
+            position: input.span_to(text).end,
+        };
+
+        let (item, rest) = match self.parser.parse(input) {
+            Ok((segment, rest)) => (Ok(segment), rest),
+            Err(error) => {
+                let rest = self.parser.recover(input, &error);
+                // the parser must make progress, otherwise the iterator would never end
+                let rest = match rest.len() < self.template.len() {
+                    true => rest,
+                    false => self
+                        .template
+                        .split_first_char()
+                        .map_or("", |(_, tail)| tail),
+                };
+                (Err(error), rest)
+            }
+        };
+
+        self.position = input.span_to(rest).end;
+        self.template = rest;
+        Some(item)
+    }
+}
@@ -75,22 +75,55 @@
 //!
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 pub mod iter;
 
 pub mod enclosed;
+
+#[cfg(feature = "std")]
+pub mod responders;
 pub use enclosed::EnclosedTemplateParser;
 
+mod check;
+mod combinators;
 mod errors;
+#[cfg(feature = "std")]
+mod memoize;
+#[cfg(feature = "std")]
+mod owned;
 mod parse;
+#[cfg(feature = "std")]
+mod partials;
+mod query;
+#[cfg(feature = "std")]
+mod registry;
 mod render;
+#[cfg(feature = "std")]
+mod respond;
 mod shorthands;
+mod span;
 mod system;
 mod template;
 
+pub use check::*;
+pub use combinators::*;
 pub use errors::*;
+#[cfg(feature = "std")]
+pub use memoize::*;
+#[cfg(feature = "std")]
+pub use owned::*;
 pub use parse::*;
+#[cfg(feature = "std")]
+pub use partials::*;
+pub use query::*;
+#[cfg(feature = "std")]
+pub use registry::*;
 pub use render::*;
+#[cfg(feature = "std")]
+pub use respond::*;
 pub use shorthands::*;
+pub use span::*;
 pub use system::*;
 pub use template::*;
 
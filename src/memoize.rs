@@ -0,0 +1,96 @@
+use core::hash::Hash;
+use std::collections::{hash_map::Entry, HashMap};
+
+/// Respond function adaptor that caches the successful responses of another respond function.
+///
+/// Repeated queries are only sent to the inner respond function once. Errors are not cached, so failed queries would be
+/// retried. The cache lives as long as the `Memoize` does, so it can be shared across multiple renders:
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{simple_curly_braces, Memoize};
+/// let mut calls = 0;
+/// let mut memoize = Memoize::new(|query| {
+///     calls += 1;
+///     match query {
+///         "user" => Ok("Alice".to_string()),
+///         _ => Err(format!("Can't answer {query:?}")),
+///     }
+/// });
+/// let system = simple_curly_braces();
+/// let output = system
+///     .lazy_parse("{user} said hello to {user}")
+///     .to_string(memoize.as_respond())
+///     .unwrap();
+/// assert_eq!(output, "Alice said hello to Alice");
+/// let output = system
+///     .lazy_parse("{user} left")
+///     .to_string(memoize.as_respond())
+///     .unwrap();
+/// assert_eq!(output, "Alice left");
+/// drop(memoize);
+/// assert_eq!(calls, 1);
+/// ```
+///
+/// To only cache within a single render, use [`into_respond`](Memoize::into_respond) instead.
+#[derive(Debug, Clone)]
+pub struct Memoize<Query, Output, Respond> {
+    respond: Respond,
+    cache: HashMap<Query, Output>,
+}
+
+impl<Query, Output, Respond> Memoize<Query, Output, Respond> {
+    /// Wrap a respond function.
+    pub fn new<Error>(respond: Respond) -> Self
+    where
+        Respond: FnMut(Query) -> Result<Output, Error>,
+    {
+        Memoize {
+            respond,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Access the cached responses.
+    pub fn cache(&self) -> &HashMap<Query, Output> {
+        &self.cache
+    }
+
+    /// Remove all cached responses.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Return the inner respond function.
+    pub fn into_inner(self) -> Respond {
+        self.respond
+    }
+}
+
+impl<Query, Output, Error, Respond> Memoize<Query, Output, Respond>
+where
+    Query: Eq + Hash + Clone,
+    Output: Clone,
+    Respond: FnMut(Query) -> Result<Output, Error>,
+{
+    /// Answer `query` from the cache, or from the inner respond function if it was not answered before.
+    pub fn respond(&mut self, query: Query) -> Result<Output, Error> {
+        match self.cache.entry(query) {
+            Entry::Occupied(entry) => Ok(entry.get().clone()),
+            Entry::Vacant(entry) => {
+                let output = (self.respond)(entry.key().clone())?;
+                Ok(entry.insert(output).clone())
+            }
+        }
+    }
+
+    /// Borrow the adaptor as a respond function, keeping the cache for later renders.
+    pub fn as_respond(&mut self) -> impl FnMut(Query) -> Result<Output, Error> + '_ {
+        |query| self.respond(query)
+    }
+
+    /// Convert the adaptor into a respond function.
+    pub fn into_respond(mut self) -> impl FnMut(Query) -> Result<Output, Error> {
+        move |query| self.respond(query)
+    }
+}
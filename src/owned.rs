@@ -0,0 +1,21 @@
+use std::{borrow::Cow, sync::Arc};
+
+/// Convert a segment, a query, or an error that borrows from the template string into one that owns its data.
+pub trait IntoOwned {
+    type Owned: 'static;
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl IntoOwned for &str {
+    type Owned = Arc<str>;
+    fn into_owned(self) -> Self::Owned {
+        Arc::from(self)
+    }
+}
+
+impl IntoOwned for Cow<'_, str> {
+    type Owned = Arc<str>;
+    fn into_owned(self) -> Arc<str> {
+        Arc::from(self)
+    }
+}
@@ -1,16 +1,28 @@
-use crate::{IntoSkipOrFatal, SkipOrFatal};
+use crate::{IntoSkipOrFatal, Position, SkipOrFatal, Span};
 
 type ParseAsComponentResult<'a, Output, Error> =
     Result<Option<(Output, &'a str)>, <Error as IntoSkipOrFatal>::Fatal>;
 
 /// Parse a segment.
-pub trait Parse<'a, Input = &'a str>: Sized {
+pub trait Parse<'a, Input = ParseInput<'a>>: Sized {
     type Output;
     type Error;
-    fn parse(&self, input: Input) -> Result<(Self::Output, &'a str), Self::Error>;
+    /// Parse a segment at the start of `input`, and return it along with the remaining text.
+    ///
+    /// The parser is borrowed for as long as the template string, so that component parsers can borrow its config.
+    fn parse(&'a self, input: Input) -> Result<(Self::Output, &'a str), Self::Error>;
+
+    /// Skip the text at the start of `input` that produces no segment, such as comments, and return the remaining
+    /// text.
+    ///
+    /// The parsing iterators call this before every segment, so that a template that ends with such text, or consists
+    /// of nothing else, produces no extra segment. Nothing is skipped by default.
+    fn skip_ignored(&'a self, input: ParseInput<'a>) -> &'a str {
+        input.text
+    }
 
     fn parse_as_component(
-        &self,
+        &'a self,
         input: Input,
     ) -> ParseAsComponentResult<'a, Self::Output, Self::Error>
     where
@@ -26,3 +38,39 @@ pub trait Parse<'a, Input = &'a str>: Sized {
         }
     }
 }
+
+/// Input of a template [parser](Parse).
+#[derive(Debug, Clone, Copy)]
+pub struct ParseInput<'a> {
+    /// The remaining part of the template string.
+    pub text: &'a str,
+    /// Position of the start of [`text`](ParseInput::text) in the whole template string.
+    pub position: Position,
+}
+
+impl<'a> ParseInput<'a> {
+    /// Create an input that starts at the beginning of a template string.
+    pub fn new(text: &'a str) -> Self {
+        ParseInput {
+            text,
+            position: Position::START,
+        }
+    }
+
+    /// Get the span of the text consumed by a parser which returned `rest` as the remaining text.
+    pub fn span_to(&self, rest: &str) -> Span {
+        Span::covering(self.position, &self.text[..(self.text.len() - rest.len())])
+    }
+}
+
+impl<'a> From<&'a str> for ParseInput<'a> {
+    fn from(text: &'a str) -> Self {
+        ParseInput::new(text)
+    }
+}
+
+/// Resume [parsing](Parse) after an error.
+pub trait Recover<'a, Input = ParseInput<'a>>: Parse<'a, Input> {
+    /// Skip the part of `input` that caused `error`, and return the remaining text from which parsing should resume.
+    fn recover(&self, input: Input, error: &Self::Error) -> &'a str;
+}
@@ -0,0 +1,213 @@
+use crate::{
+    enclosed::{
+        partial::{self, PartialSegment},
+        PartialParser, SegmentDisplay,
+    },
+    iter::LazyParseIter,
+    Parse, Render, TemplateSystem,
+};
+use core::fmt;
+use derive_more::{Display, Error};
+use pipe_trait::Pipe;
+
+/// Render templates whose partials, such as `{>header}`, include other templates.
+///
+/// The partials are parsed by a [`PartialParser`]. The template of each partial is loaded by name, then parsed by the
+/// same [`TemplateSystem`] and rendered with the same respond function. Partials can include other partials, up to a
+/// [maximum depth](Partials::with_max_depth):
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{
+///     enclosed::{Parser, PartialParser, SimpleEscapeParser, SimpleQuery, SimpleQueryParser},
+///     IntoTemplateSystem,
+/// };
+/// use std::borrow::Cow;
+/// let parser = Parser::curly_braces()
+///     .with_escape_parser(SimpleEscapeParser)
+///     .with_query_parser(SimpleQueryParser);
+/// let system = PartialParser::new(parser).into_template_system::<SimpleQuery>();
+/// let partials = system.partials(|name: &str| match name {
+///     "header" => Ok(Cow::Borrowed("Dear {name},\n")),
+///     "footer" => Ok(Cow::Owned(String::from("----\n{>signature}"))),
+///     "signature" => Ok(Cow::Borrowed("{sender}")),
+///     _ => Err(format!("Unknown partial {name:?}")),
+/// });
+/// let respond = |query: &str| match query {
+///     "name" => Ok("Alice"),
+///     "sender" => Ok("Bob"),
+///     _ => Err(format!("Can't answer {query:?}")),
+/// };
+/// let output = partials
+///     .to_string("{>header}Your order has shipped.\n{>footer}", respond)
+///     .unwrap();
+/// assert_eq!(output, "Dear Alice,\nYour order has shipped.\n----\nBob");
+/// ```
+///
+/// The respond function must accept queries of any lifetime, because the queries of a partial borrow its template,
+/// which only lives while the partial is rendered. A closure only does so if its parameter is annotated (e.g.
+/// `|query: &str| ...`) and it is defined before being passed, as above.
+#[derive(Debug)]
+pub struct Partials<'s, Parser, Query, Load> {
+    system: &'s TemplateSystem<Parser, Query>,
+    load: Load,
+    max_depth: usize,
+}
+
+// Implemented by hand because the system is borrowed, so the parser needs not be `Clone` or `Copy`.
+impl<Parser, Query, Load: Clone> Clone for Partials<'_, Parser, Query, Load> {
+    fn clone(&self) -> Self {
+        Partials {
+            system: self.system,
+            load: self.load.clone(),
+            max_depth: self.max_depth,
+        }
+    }
+}
+
+impl<Parser, Query, Load: Copy> Copy for Partials<'_, Parser, Query, Load> {}
+
+impl<Inner, Query> TemplateSystem<PartialParser<Inner>, Query> {
+    /// Render templates with partials whose templates are loaded by `load`.
+    ///
+    /// _see more:_ [`Partials`].
+    pub fn partials<Load>(&self, load: Load) -> Partials<'_, PartialParser<Inner>, Query, Load> {
+        Partials {
+            system: self,
+            load,
+            max_depth: 16,
+        }
+    }
+}
+
+/// Error returned by the rendering functions of [`Partials`].
+#[derive(Debug, Display, Error)]
+pub enum PartialError<ParseError, QueryError, LoadError> {
+    /// The main template failed to parse.
+    #[display("{_0}")]
+    Parse(ParseError),
+    /// The template of a partial failed to parse.
+    #[display("Failed to parse partial {name:?}: {error}")]
+    ParsePartial { name: String, error: ParseError },
+    /// The loader failed to load the template of a partial.
+    #[display("Failed to load partial {name:?}: {error}")]
+    Load { name: String, error: LoadError },
+    /// A partial includes itself, directly or through other partials.
+    #[display("Partial {_0:?} is included recursively")]
+    Cycle(#[error(not(source))] String),
+    /// The partials are nested deeper than the [maximum depth](Partials::with_max_depth).
+    #[display("Partials are nested deeper than {_0} levels")]
+    DepthLimit(#[error(not(source))] usize),
+    /// The respond function failed to answer a query.
+    #[display("{_0}")]
+    Query(QueryError),
+    /// Failed to write to the output.
+    #[display("{_0}")]
+    Write(fmt::Error),
+}
+
+impl<Parser, Query, Load> Partials<'_, Parser, Query, Load> {
+    /// Replace the maximum number of nested partials, which is 16 by default.
+    ///
+    /// Including a partial deeper than this fails with [`PartialError::DepthLimit`].
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+impl<Inner, Query, Load> Partials<'_, PartialParser<Inner>, Query, Load> {
+    /// Render `text` and the partials it includes, and write the result to a buffer that implements [`fmt::Write`].
+    ///
+    /// Like [`lazy_parse`](TemplateSystem::lazy_parse), each segment is only parsed just before it is rendered, so the
+    /// output may be partially written when an error occurs.
+    pub fn write_to<Output, Text, SegmentError, QueryOutput, QueryError, LoadError, Respond>(
+        &self,
+        output: &mut Output,
+        text: &str,
+        mut respond: Respond,
+    ) -> Result<(), PartialError<partial::ParseError<SegmentError>, QueryError, LoadError>>
+    where
+        Output: fmt::Write,
+        for<'t> Inner: Parse<'t, Error = SegmentError>,
+        for<'t> <Inner as Parse<'t>>::Output:
+            Render<Respond, SegmentDisplay<QueryOutput, &'t str>, QueryError>,
+        QueryOutput: fmt::Display,
+        Load: Fn(&str) -> Result<Text, LoadError>,
+        Text: AsRef<str>,
+    {
+        self.render(output, text, &mut respond, &mut Vec::new())
+    }
+
+    /// Render `text` and the partials it includes into a string.
+    pub fn to_string<Text, SegmentError, QueryOutput, QueryError, LoadError, Respond>(
+        &self,
+        text: &str,
+        respond: Respond,
+    ) -> Result<String, PartialError<partial::ParseError<SegmentError>, QueryError, LoadError>>
+    where
+        for<'t> Inner: Parse<'t, Error = SegmentError>,
+        for<'t> <Inner as Parse<'t>>::Output:
+            Render<Respond, SegmentDisplay<QueryOutput, &'t str>, QueryError>,
+        QueryOutput: fmt::Display,
+        Load: Fn(&str) -> Result<Text, LoadError>,
+        Text: AsRef<str>,
+    {
+        let mut output = String::new();
+        self.write_to(&mut output, text, respond)?;
+        Ok(output)
+    }
+
+    /// Render `text`, which is the template of the last partial in `stack` (or the main template if `stack` is empty).
+    fn render<Output, Text, SegmentError, QueryOutput, QueryError, LoadError, Respond>(
+        &self,
+        output: &mut Output,
+        text: &str,
+        respond: &mut Respond,
+        stack: &mut Vec<String>,
+    ) -> Result<(), PartialError<partial::ParseError<SegmentError>, QueryError, LoadError>>
+    where
+        Output: fmt::Write,
+        for<'t> Inner: Parse<'t, Error = SegmentError>,
+        for<'t> <Inner as Parse<'t>>::Output:
+            Render<Respond, SegmentDisplay<QueryOutput, &'t str>, QueryError>,
+        QueryOutput: fmt::Display,
+        Load: Fn(&str) -> Result<Text, LoadError>,
+        Text: AsRef<str>,
+    {
+        for segment in LazyParseIter::new(text, &self.system.parser) {
+            let segment = segment.map_err(|error| match stack.last() {
+                Some(name) => PartialError::ParsePartial {
+                    name: name.clone(),
+                    error,
+                },
+                None => PartialError::Parse(error),
+            })?;
+
+            let name = match segment {
+                PartialSegment::Segment(segment) => {
+                    let response = segment.render(respond).map_err(PartialError::Query)?;
+                    write!(output, "{response}").map_err(PartialError::Write)?;
+                    continue;
+                }
+                PartialSegment::Partial(name, _) => name,
+            };
+
+            if stack.iter().any(|included| included == name) {
+                return name.to_string().pipe(PartialError::Cycle).pipe(Err);
+            }
+            if stack.len() >= self.max_depth {
+                return PartialError::DepthLimit(self.max_depth).pipe(Err);
+            }
+            let partial = (self.load)(name).map_err(|error| PartialError::Load {
+                name: name.to_string(),
+                error,
+            })?;
+            stack.push(name.to_string());
+            self.render(output, partial.as_ref(), respond, stack)?;
+            stack.pop();
+        }
+
+        Ok(())
+    }
+}
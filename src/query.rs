@@ -0,0 +1,6 @@
+/// Get the query of a segment without [rendering](crate::Render) it.
+pub trait GetQuery {
+    type Query;
+    /// Return the query if the segment is an expression, or `None` otherwise.
+    fn query(&self) -> Option<Self::Query>;
+}
@@ -0,0 +1,197 @@
+use crate::{
+    iter::{ParsedTemplate, RecoveringParseIter},
+    IntoOwned, Parse, Recover, Render, TemplateApplicationError, TemplateSystem,
+};
+use core::fmt;
+use derive_more::{Display, Error};
+use std::collections::HashMap;
+
+type OwnedParsedTemplate<Segment, Query> =
+    ParsedTemplate<Vec<Segment>, <Query as IntoOwned>::Owned>;
+
+/// Store of templates that were parsed ahead of time, looked up by name.
+///
+/// Each template is parsed once when it is [inserted](TemplateRegistry::insert), and the parsed template owns its
+/// segments so that the template string can be dropped. Inserting a template under an existing name replaces that
+/// entry alone:
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{enclosed::OwnedSimpleQuery, simple_curly_braces, TemplateRegistry};
+/// let mut registry = TemplateRegistry::new(simple_curly_braces());
+/// registry.insert("greeting", "Hello, {name}!").unwrap();
+/// registry.insert("farewell", "Goodbye, {name}.").unwrap();
+/// let respond = |query: OwnedSimpleQuery| match query.as_ref() {
+///     "name" => Ok("Alice"),
+///     _ => Err(format!("Can't answer {query:?}")),
+/// };
+/// assert_eq!(registry.render("greeting", respond).unwrap(), "Hello, Alice!");
+/// registry.insert("greeting", "Hi, {name}!").unwrap();
+/// assert_eq!(registry.render("greeting", respond).unwrap(), "Hi, Alice!");
+/// assert_eq!(registry.render("farewell", respond).unwrap(), "Goodbye, Alice.");
+/// ```
+///
+/// A template that fails to parse is not inserted, and every parse error is reported:
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{simple_curly_braces, GetSpan, TemplateRegistry};
+/// let mut registry = TemplateRegistry::new(simple_curly_braces());
+/// registry.insert("greeting", "Hello, {name}!").unwrap();
+/// let errors = registry.insert("greeting", "Hello, {name}} {age").unwrap_err();
+/// let errors: Vec<_> = errors
+///     .iter()
+///     .map(|error| format!("{}: {error}", error.span()))
+///     .collect();
+/// assert_eq!(
+///     errors,
+///     [
+///         "1:14: Failed to parse query: Unexpected token \"}\"",
+///         "1:20: Failed to parse query: Unexpected end of input",
+///     ],
+/// );
+/// assert!(registry.contains("greeting")); // the previous template is kept
+/// ```
+#[derive(Debug, Clone)]
+pub struct TemplateRegistry<Parser, Query, Segment>
+where
+    Query: IntoOwned,
+{
+    system: TemplateSystem<Parser, Query>,
+    templates: HashMap<String, ParsedTemplate<Vec<Segment>, Query::Owned>>,
+}
+
+/// Error returned by [`TemplateRegistry::render`] and [`TemplateRegistry::write_to`].
+#[derive(Debug, Display, Error, Clone, PartialEq, Eq)]
+pub enum RegistryRenderError<RenderError> {
+    /// No template was [inserted](TemplateRegistry::insert) under the requested name.
+    #[display("No template is named {_0:?}")]
+    UnknownTemplate(#[error(not(source))] String),
+    /// The respond function failed to answer a query, or the response failed to render.
+    #[display("{_0}")]
+    Query(RenderError),
+    /// Failed to write to the output.
+    #[display("{_0}")]
+    Write(fmt::Error),
+}
+
+impl<Parser, Query, Segment> TemplateRegistry<Parser, Query, Segment>
+where
+    Query: IntoOwned,
+{
+    /// Create an empty registry whose templates are parsed by `system`.
+    pub fn new(system: TemplateSystem<Parser, Query>) -> Self
+    where
+        for<'a> Parser: Parse<'a>,
+        for<'a> <Parser as Parse<'a>>::Output: IntoOwned<Owned = Segment>,
+    {
+        TemplateRegistry {
+            system,
+            templates: HashMap::new(),
+        }
+    }
+
+    /// Get the parsed template of `name`.
+    pub fn get(&self, name: &str) -> Option<&OwnedParsedTemplate<Segment, Query>> {
+        self.templates.get(name)
+    }
+
+    /// Check whether a template is named `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.templates.contains_key(name)
+    }
+
+    /// List the names of the templates in arbitrary order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    /// Number of templates.
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Check whether there are no templates.
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Remove the template of `name`, and return it if it exists.
+    pub fn remove(&mut self, name: &str) -> Option<OwnedParsedTemplate<Segment, Query>> {
+        self.templates.remove(name)
+    }
+
+    /// Parse `text` and store it as the template of `name`, replacing the previous template of `name` if there is one.
+    ///
+    /// If `text` fails to parse, all the parse errors are returned and the registry is left unchanged. Otherwise, the
+    /// previous template of `name` is returned if there is one.
+    ///
+    /// The parser must [recover](Recover) from errors to report all of them, and its segments must be convertible
+    /// into [owned](IntoOwned) segments.
+    pub fn insert<ParseError>(
+        &mut self,
+        name: impl Into<String>,
+        text: &str,
+    ) -> Result<Option<OwnedParsedTemplate<Segment, Query>>, Vec<ParseError>>
+    where
+        for<'a> Parser: Recover<'a>,
+        for<'a> <Parser as Parse<'a>>::Output: IntoOwned<Owned = Segment>,
+        for<'a> <Parser as Parse<'a>>::Error: IntoOwned<Owned = ParseError>,
+    {
+        let mut errors = Vec::new();
+        let segments: Vec<Segment> = RecoveringParseIter::new(text, &self.system.parser)
+            .filter_map(|segment| match segment {
+                Ok(segment) => Some(segment.into_owned()),
+                Err(error) => {
+                    errors.push(error.into_owned());
+                    None
+                }
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        let template = ParsedTemplate::new(segments);
+        Ok(self.templates.insert(name.into(), template))
+    }
+
+    /// Render the template of `name` into a [`String`].
+    pub fn render<RenderOutput, RenderError, QueryOutput, QueryError, Respond>(
+        &self,
+        name: &str,
+        respond: Respond,
+    ) -> Result<String, RegistryRenderError<RenderError>>
+    where
+        Segment: Clone + Render<Respond, RenderOutput, RenderError>,
+        RenderOutput: fmt::Display,
+        Respond: FnMut(Query::Owned) -> Result<QueryOutput, QueryError>,
+    {
+        let mut output = String::new();
+        self.write_to(&mut output, name, respond)?;
+        Ok(output)
+    }
+
+    /// Render the template of `name`, and write the result to a buffer that implements [`fmt::Write`].
+    pub fn write_to<Output, RenderOutput, RenderError, QueryOutput, QueryError, Respond>(
+        &self,
+        output: &mut Output,
+        name: &str,
+        respond: Respond,
+    ) -> Result<(), RegistryRenderError<RenderError>>
+    where
+        Output: fmt::Write,
+        Segment: Clone + Render<Respond, RenderOutput, RenderError>,
+        RenderOutput: fmt::Display,
+        Respond: FnMut(Query::Owned) -> Result<QueryOutput, QueryError>,
+    {
+        self.get(name)
+            .ok_or_else(|| RegistryRenderError::UnknownTemplate(name.to_string()))?
+            .to_template()
+            .write_to(output, respond)
+            .map_err(|error| match error {
+                TemplateApplicationError::Parse(error) => match error {},
+                TemplateApplicationError::Query(error) => RegistryRenderError::Query(error),
+                TemplateApplicationError::Write(error) => RegistryRenderError::Write(error),
+            })
+    }
+}
@@ -1,5 +1,13 @@
-/// Represent the ability of a segment of a string template to render template output.
+use core::future::Future;
+
+/// Represent the ability of a segment (or a query within a segment) of a string template to render template output.
 pub trait Render<Respond, Output, Error> {
     /// Optionally send a query the respond function for output, then render.
     fn render(self, respond: &mut Respond) -> Result<Output, Error>;
 }
+
+/// Asynchronous counterpart of [`Render`] for respond functions that return [futures](Future).
+pub trait AsyncRender<Respond, Output, Error> {
+    /// Optionally send a query to the respond function and await its output, then render.
+    fn render_async(self, respond: &mut Respond) -> impl Future<Output = Result<Output, Error>>;
+}
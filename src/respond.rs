@@ -0,0 +1,98 @@
+use core::fmt;
+use derive_more::{Display, Error};
+use std::collections::{BTreeMap, HashMap};
+
+/// Answer queries with the fields of a value.
+///
+/// This trait is usually implemented with `#[derive(RespondFields)]` from the `lazy-template-macros` crate, which maps the
+/// query `name` to the field `name`, and the query `address.city` to the query `city` of the field `address`.
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{simple_curly_braces, RespondFields, UnknownQuery};
+/// use std::fmt;
+///
+/// struct Person {
+///     name: &'static str,
+///     age: u32,
+/// }
+///
+/// impl RespondFields for Person {
+///     fn respond(&self, query: &str) -> Result<&dyn fmt::Display, UnknownQuery> {
+///         match query {
+///             "name" => Ok(&self.name),
+///             "age" => Ok(&self.age),
+///             _ => Err(UnknownQuery::new(query)),
+///         }
+///     }
+/// }
+///
+/// let alice = Person { name: "Alice", age: 20 };
+/// let output = simple_curly_braces()
+///     .lazy_parse("{name} is {age} years old")
+///     .to_string(alice.responder())
+///     .unwrap();
+/// assert_eq!(output, "Alice is 20 years old");
+/// ```
+pub trait RespondFields {
+    /// Get the value that answers `query`.
+    fn respond(&self, query: &str) -> Result<&dyn fmt::Display, UnknownQuery>;
+
+    /// Create a respond function to be passed to a [`Template`](crate::Template).
+    fn responder<'r>(&'r self) -> impl FnMut(&str) -> Result<&'r dyn fmt::Display, UnknownQuery>
+    where
+        Self: Sized,
+    {
+        move |query| self.respond(query)
+    }
+}
+
+impl<Value> RespondFields for &Value
+where
+    Value: RespondFields + ?Sized,
+{
+    fn respond(&self, query: &str) -> Result<&dyn fmt::Display, UnknownQuery> {
+        Value::respond(self, query)
+    }
+}
+
+/// Answer queries with the values of their keys.
+impl<Value> RespondFields for HashMap<String, Value>
+where
+    Value: fmt::Display,
+{
+    fn respond(&self, query: &str) -> Result<&dyn fmt::Display, UnknownQuery> {
+        self.get(query)
+            .map(|value| value as &dyn fmt::Display)
+            .ok_or_else(|| UnknownQuery::new(query))
+    }
+}
+
+/// Answer queries with the values of their keys.
+impl<Value> RespondFields for BTreeMap<String, Value>
+where
+    Value: fmt::Display,
+{
+    fn respond(&self, query: &str) -> Result<&dyn fmt::Display, UnknownQuery> {
+        self.get(query)
+            .map(|value| value as &dyn fmt::Display)
+            .ok_or_else(|| UnknownQuery::new(query))
+    }
+}
+
+/// Error returned by [`RespondFields::respond`] when the query does not match any field.
+#[derive(Debug, Display, Error, Clone, PartialEq, Eq)]
+#[display("Unknown query {query:?}")]
+pub struct UnknownQuery {
+    #[error(not(source))]
+    pub query: String,
+}
+
+impl UnknownQuery {
+    /// Create an error for `query`.
+    pub fn new(query: impl Into<String>) -> Self {
+        UnknownQuery {
+            query: query.into(),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Ready-made responders for common sources of answers.
+//!
+//! Each function in this module creates a respond function that can be passed to a [`Template`](crate::Template):
+//!
+//! ```
+//! # use pretty_assertions::assert_eq;
+//! use lazy_template::{responders, simple_curly_braces};
+//! use std::collections::HashMap;
+//! let map = HashMap::from([("name", "Alice"), ("age", "20")]);
+//! let output = simple_curly_braces()
+//!     .lazy_parse("{name} is {age} years old")
+//!     .to_string(responders::from_map(&map))
+//!     .unwrap();
+//! assert_eq!(output, "Alice is 20 years old");
+//! ```
+//!
+//! Only the queries with a certain prefix can be sent to a responder with [`strip_prefix`], and responders can be
+//! combined with the methods of [`ResponderExt`](crate::ResponderExt).
+
+mod env;
+mod file;
+mod func;
+mod map;
+mod positional;
+mod prefix;
+
+pub use env::*;
+pub use file::*;
+pub use func::*;
+pub use map::*;
+pub use positional::*;
+pub use prefix::*;
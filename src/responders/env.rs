@@ -0,0 +1,21 @@
+use derive_more::{Display, Error};
+use std::env;
+
+/// Error returned by [`from_env`].
+#[derive(Debug, Display, Error, Clone, PartialEq, Eq)]
+#[display("Cannot read environment variable {name:?}: {error}")]
+pub struct EnvError {
+    pub name: String,
+    #[error(source)]
+    pub error: env::VarError,
+}
+
+/// Answer each query with the value of the environment variable of the same name.
+pub fn from_env() -> impl FnMut(&str) -> Result<String, EnvError> {
+    |name| {
+        env::var(name).map_err(|error| EnvError {
+            name: name.to_string(),
+            error,
+        })
+    }
+}
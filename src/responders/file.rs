@@ -0,0 +1,21 @@
+use derive_more::{Display, Error};
+use std::{fs, io};
+
+/// Error returned by [`from_file`].
+#[derive(Debug, Display, Error)]
+#[display("Failed to read file {path:?}: {error}")]
+pub struct FileError {
+    pub path: String,
+    #[error(source)]
+    pub error: io::Error,
+}
+
+/// Answer each query with the content of the text file whose path is the query.
+pub fn from_file() -> impl FnMut(&str) -> Result<String, FileError> {
+    |path| {
+        fs::read_to_string(path).map_err(|error| FileError {
+            path: path.to_string(),
+            error,
+        })
+    }
+}
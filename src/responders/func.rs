@@ -0,0 +1,9 @@
+use crate::UnknownQuery;
+
+/// Answer each query with the value returned by `get`, treating `None` as an [`UnknownQuery`].
+pub fn from_fn<Output, Get>(mut get: Get) -> impl FnMut(&str) -> Result<Output, UnknownQuery>
+where
+    Get: FnMut(&str) -> Option<Output>,
+{
+    move |query| get(query).ok_or_else(|| UnknownQuery::new(query))
+}
@@ -0,0 +1,42 @@
+use crate::UnknownQuery;
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Map whose values can be looked up by string keys.
+pub trait Lookup {
+    type Value;
+    /// Get the value of `key`.
+    fn lookup(&self, key: &str) -> Option<&Self::Value>;
+}
+
+impl<Key, Value, Hasher> Lookup for HashMap<Key, Value, Hasher>
+where
+    Key: Borrow<str> + Hash + Eq,
+    Hasher: BuildHasher,
+{
+    type Value = Value;
+    fn lookup(&self, key: &str) -> Option<&Self::Value> {
+        self.get(key)
+    }
+}
+
+impl<Key, Value> Lookup for BTreeMap<Key, Value>
+where
+    Key: Borrow<str> + Ord,
+{
+    type Value = Value;
+    fn lookup(&self, key: &str) -> Option<&Self::Value> {
+        self.get(key)
+    }
+}
+
+/// Answer each query with the value of the same key in `map`.
+pub fn from_map<'m, Map>(map: &'m Map) -> impl FnMut(&str) -> Result<&'m Map::Value, UnknownQuery>
+where
+    Map: Lookup,
+{
+    move |query| map.lookup(query).ok_or_else(|| UnknownQuery::new(query))
+}
@@ -0,0 +1,26 @@
+use derive_more::{Display, Error};
+
+/// Error returned by [`positional`].
+#[derive(Debug, Display, Error, Clone, Copy, PartialEq, Eq)]
+#[display("Positional argument at {index} is not provided")]
+pub struct MissingPositional {
+    #[error(not(source))]
+    pub index: usize,
+}
+
+/// Answer the queries with the items of `args` in order, regardless of the queries themselves.
+///
+/// This is usually combined with [`strip_prefix`](super::strip_prefix) or other responders so that only some queries
+/// (such as `{}`) are answered positionally.
+pub fn positional<Args>(args: Args) -> impl FnMut(&str) -> Result<Args::Item, MissingPositional>
+where
+    Args: IntoIterator,
+{
+    let mut args = args.into_iter();
+    let mut index = 0;
+    move |_| {
+        let arg = args.next().ok_or(MissingPositional { index });
+        index += 1;
+        arg
+    }
+}
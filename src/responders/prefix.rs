@@ -0,0 +1,44 @@
+use crate::UnknownQuery;
+use derive_more::{Display, Error};
+use pipe_trait::Pipe;
+
+/// Error returned by [`strip_prefix`].
+#[derive(Debug, Display, Error, Clone, PartialEq, Eq)]
+pub enum PrefixError<Error> {
+    /// The query does not start with the prefix.
+    #[display("{_0}")]
+    Mismatch(#[error(not(source))] UnknownQuery),
+    /// The inner respond function failed.
+    #[display("{_0}")]
+    Respond(#[error(not(source))] Error),
+}
+
+/// Only answer the queries that start with `prefix`, by sending the rest of each query to `respond`.
+///
+/// ```
+/// # use pretty_assertions::assert_eq;
+/// use lazy_template::{responders, simple_curly_braces};
+/// use std::collections::BTreeMap;
+/// let map = BTreeMap::from([("name", "Alice")]);
+/// let output = simple_curly_braces()
+///     .lazy_parse("Hello, {map:name}!")
+///     .to_string(responders::strip_prefix("map:", responders::from_map(&map)))
+///     .unwrap();
+/// assert_eq!(output, "Hello, Alice!");
+/// ```
+pub fn strip_prefix<'q, Prefix, Output, Error, Respond>(
+    prefix: Prefix,
+    mut respond: Respond,
+) -> impl FnMut(&'q str) -> Result<Output, PrefixError<Error>>
+where
+    Prefix: AsRef<str>,
+    Respond: FnMut(&'q str) -> Result<Output, Error>,
+{
+    move |query| match query.strip_prefix(prefix.as_ref()) {
+        Some(rest) => respond(rest).map_err(PrefixError::Respond),
+        None => query
+            .pipe(UnknownQuery::new)
+            .pipe(PrefixError::Mismatch)
+            .pipe(Err),
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     enclosed::{
         self, simple_escape, simple_query, SimpleEnclosedTemplateSystem, SimpleEscapeParser,
-        SimpleQueryParser,
+        SimpleQuery, SimpleQueryParser,
     },
     iter::{EagerParseIter, LazyParseIter, ParsedTemplate},
     EnclosedTemplateParser, IntoTemplateSystem, Template, TemplateApplicationError,
@@ -84,6 +84,9 @@ pub type LazilyParsedApplicationError<QueryError> = TemplateApplicationError<
 /// Value type of [`SimpleCurlyBraces::eager_parse`].
 pub type EagerlyParsed<SegmentContainer, Query> = ParsedTemplate<SegmentContainer, Query>;
 
+/// Segment type of [`EagerlyParsed`].
+pub type EagerlyParsedSegment<'a> = enclosed::Segment<SimpleQuery<'a>, &'a str>;
+
 /// Error type of [`SimpleCurlyBraces::eager_parse`].
 pub type EagerParseError =
     enclosed::ParseError<simple_escape::ParseError, simple_query::ParseError>;
@@ -107,7 +110,8 @@ pub type EagerlyParsedApplicationError<QueryError> =
 mod std_tests {
     use super::{
         simple_curly_braces, EagerParseError, EagerlyParsed, EagerlyParsedApplicationError,
-        EagerlyParsedTemplate, LazilyParsed, LazilyParsedApplicationError, SimpleCurlyBraces,
+        EagerlyParsedSegment, EagerlyParsedTemplate, LazilyParsed, LazilyParsedApplicationError,
+        SimpleCurlyBraces,
     };
     use derive_more::{Display, Error};
 
@@ -125,7 +129,7 @@ mod std_tests {
             drop(lazy_result);
 
             let eager_parsed_template: Result<
-                EagerlyParsed<Vec<crate::enclosed::Segment<&str>>, &str>,
+                EagerlyParsed<Vec<EagerlyParsedSegment<'_>>, &str>,
                 EagerParseError,
             > = system.eager_parse::<Vec<_>>("");
             let eager_parsed_template: EagerlyParsedTemplate<_, &str> =
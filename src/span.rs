@@ -0,0 +1,77 @@
+use derive_more::Display;
+
+/// Location of a character in a template string.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[display("{line}:{column}")]
+pub struct Position {
+    /// Number of bytes from the start of the template string.
+    pub offset: usize,
+    /// Line number, starting from 1.
+    pub line: usize,
+    /// Column number (counted in characters), starting from 1.
+    pub column: usize,
+}
+
+impl Position {
+    /// Position of the first character of a template string.
+    pub const START: Self = Position {
+        offset: 0,
+        line: 1,
+        column: 1,
+    };
+
+    /// Get the position right after `text`, assuming `text` starts at `self`.
+    pub fn advance(mut self, text: &str) -> Self {
+        for char in text.chars() {
+            self.offset += char.len_utf8();
+            if char == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Position::START
+    }
+}
+
+/// Range of a template string occupied by a segment or an error.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[display("{start}")]
+pub struct Span {
+    /// Position of the first character.
+    pub start: Position,
+    /// Position right after the last character.
+    pub end: Position,
+}
+
+impl Span {
+    /// Create a span that starts at `start` and covers `text`.
+    pub fn covering(start: Position, text: &str) -> Self {
+        Span {
+            start,
+            end: start.advance(text),
+        }
+    }
+
+    /// Create an empty span at `position`.
+    pub fn empty(position: Position) -> Self {
+        Span {
+            start: position,
+            end: position,
+        }
+    }
+}
+
+/// Get the [`Span`] of a segment or an error.
+pub trait GetSpan {
+    fn span(&self) -> Span;
+}
@@ -1,13 +1,16 @@
 use crate::{
-    iter::{LazyParseIter, ParsedTemplate},
-    Parse, Template,
+    iter::{LazyParseIter, ParsedTemplate, RecoveringParseIter},
+    Parse, Recover, Template,
 };
+
+#[cfg(feature = "std")]
+use crate::IntoOwned;
 use core::marker::PhantomData;
 use pipe_trait::Pipe;
 
 #[derive(Debug, Clone, Copy)]
 pub struct TemplateSystem<Parser, Query> {
-    parser: Parser,
+    pub(crate) parser: Parser,
     _query: PhantomData<Query>, // phantom Query is necessary to enable type inference later on
 }
 
@@ -65,7 +68,7 @@ where
     ///     .unwrap_err();
     /// # assert_eq!(
     /// #     error.to_string(),
-    /// #     "Failed to parse query: Unexpected token '}'"
+    /// #     "Failed to parse query: Unexpected token \"}\""
     /// # );
     /// assert_eq!(output, "Alice is a 20 years "); // output is partially written
     /// # }
@@ -126,6 +129,105 @@ where
     }
 }
 
+impl<'a, Parser, Query> TemplateSystem<Parser, Query>
+where
+    Parser: Recover<'a>,
+{
+    /// Parse the template string ahead of time, without stopping at the first error.
+    ///
+    /// Whenever a segment fails to parse, the error is collected and the parser skips to the next segment. The segments
+    /// that were parsed successfully are returned alongside all the errors:
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "std"))] fn main() {}
+    /// # #[cfg(feature = "std")] fn main() {
+    /// # use pretty_assertions::assert_eq;
+    /// use lazy_template::GetSpan;
+    /// let system = lazy_template::simple_curly_braces();
+    /// let (parsed_template, errors) = system
+    ///     .recovering_parse::<Vec<_>, Vec<_>>(r"Hello, {name}} \z {age");
+    /// let output = parsed_template
+    ///     .to_template()
+    ///     .to_string(|query| (query == "name").then_some("Alice").ok_or("Invalid query"))
+    ///     .unwrap();
+    /// assert_eq!(output, "Hello, Alice  ");
+    /// let errors: Vec<_> = errors
+    ///     .iter()
+    ///     .map(|error| format!("{}: {error}", error.span()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     errors,
+    ///     [
+    ///         "1:14: Failed to parse query: Unexpected token \"}\"",
+    ///         "1:17: Failed to escape: Unsupported escape code 'z'",
+    ///         "1:23: Failed to parse query: Unexpected end of input",
+    ///     ],
+    /// );
+    /// # }
+    /// ```
+    pub fn recovering_parse<SegmentContainer, ErrorContainer>(
+        &'a self,
+        text: &'a str,
+    ) -> (ParsedTemplate<SegmentContainer, Query>, ErrorContainer)
+    where
+        SegmentContainer: FromIterator<Parser::Output>,
+        ErrorContainer: Default + Extend<Parser::Error>,
+    {
+        let mut errors = ErrorContainer::default();
+        let segments = RecoveringParseIter::new(text, &self.parser)
+            .filter_map(|segment| match segment {
+                Ok(segment) => Some(segment),
+                Err(error) => {
+                    errors.extend([error]);
+                    None
+                }
+            })
+            .collect::<SegmentContainer>()
+            .pipe(ParsedTemplate::new);
+        (segments, errors)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Parser, Query> TemplateSystem<Parser, Query>
+where
+    for<'a> Parser: Parse<'a>,
+    Query: IntoOwned,
+{
+    /// Parse an owned template string ahead of time into a parsed template that owns its segments.
+    ///
+    /// Unlike [`eager_parse`](Self::eager_parse), the returned parsed template does not borrow the template string,
+    /// so it can be cached or sent to other threads:
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// let system = lazy_template::simple_curly_braces();
+    /// let parsed_template = system
+    ///     .eager_parse_owned::<Vec<_>, _, _>(String::from("Hello, {name}!"))
+    ///     .unwrap();
+    /// let output = parsed_template
+    ///     .to_template()
+    ///     .to_string(|query| (query.as_ref() == "name").then_some("Alice").ok_or("Invalid query"))
+    ///     .unwrap();
+    /// assert_eq!(output, "Hello, Alice!");
+    /// ```
+    pub fn eager_parse_owned<SegmentContainer, Segment, Error>(
+        &self,
+        text: String,
+    ) -> Result<ParsedTemplate<SegmentContainer, Query::Owned>, Error>
+    where
+        for<'a> <Parser as Parse<'a>>::Output: IntoOwned<Owned = Segment>,
+        for<'a> <Parser as Parse<'a>>::Error: IntoOwned<Owned = Error>,
+        SegmentContainer: FromIterator<Segment>,
+    {
+        LazyParseIter::new(&text, &self.parser)
+            .map(|segment| segment.map(IntoOwned::into_owned))
+            .map(|segment| segment.map_err(IntoOwned::into_owned))
+            .collect::<Result<SegmentContainer, Error>>()
+            .map(ParsedTemplate::new)
+    }
+}
+
 /// Convert a [parser](Parse) into a [`TemplateSystem`].
 pub trait IntoTemplateSystem: Sized {
     fn into_template_system<Query>(self) -> TemplateSystem<Self, Query> {
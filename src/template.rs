@@ -1,5 +1,5 @@
-use crate::Render;
-use core::{convert::Infallible, fmt, marker::PhantomData};
+use crate::{AsyncRender, GetQuery, Render};
+use core::{convert::Infallible, fmt, future::Future, marker::PhantomData};
 use derive_more::{Display, Error, IntoIterator};
 use pipe_trait::Pipe;
 
@@ -20,6 +20,7 @@ impl<SegmentResultIntoIter, Query> Template<SegmentResultIntoIter, Query> {
 }
 
 #[derive(Debug, Display, Error, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TemplateApplicationError<ParseError, QueryError, WriteError> {
     Parse(ParseError),
     Query(QueryError),
@@ -32,16 +33,25 @@ where
 {
     /// Apply the template, and write the resulting segment outputs that implement [`fmt::Display`] to a
     /// buffer that implements [`fmt::Write`].
-    pub fn write_to<Output, Segment, ParseError, RenderOutput, QueryOutput, QueryError, Respond>(
+    pub fn write_to<
+        Output,
+        Segment,
+        ParseError,
+        RenderOutput,
+        RenderError,
+        QueryOutput,
+        QueryError,
+        Respond,
+    >(
         self,
         output: &mut Output,
         respond: Respond,
-    ) -> Result<(), TemplateApplicationError<ParseError, QueryError, fmt::Error>>
+    ) -> Result<(), TemplateApplicationError<ParseError, RenderError, fmt::Error>>
     where
         Output: fmt::Write,
         SegmentResultIntoIter::Item: Into<Result<Segment, ParseError>>,
         RenderOutput: fmt::Display,
-        Segment: Render<Respond, RenderOutput, QueryError>,
+        Segment: Render<Respond, RenderOutput, RenderError>,
         Respond: FnMut(Query) -> Result<QueryOutput, QueryError>,
     {
         let mut write_error = None;
@@ -62,11 +72,128 @@ where
         Ok(())
     }
 
+    /// Apply the template with a respond function that returns [futures](Future), and write the resulting segment
+    /// outputs that implement [`fmt::Display`] to a buffer that implements [`fmt::Write`].
+    ///
+    /// The queries are sent one at a time, each response is awaited before the next segment is parsed.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "std"))] fn main() {}
+    /// # #[cfg(feature = "std")] fn main() {
+    /// # use pretty_assertions::assert_eq;
+    /// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    /// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+    /// #     let mut future = std::pin::pin!(future);
+    /// #     loop {
+    /// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+    /// #             return output;
+    /// #         }
+    /// #     }
+    /// # }
+    /// let system = lazy_template::simple_curly_braces();
+    /// let mut output = String::new();
+    /// let future = system
+    ///     .lazy_parse("{name} is {age} years old")
+    ///     .write_to_async(&mut output, |query| async move {
+    ///         match query {
+    ///             "name" => Ok("Alice"),
+    ///             "age" => Ok("20"),
+    ///             _ => Err(format!("Can't answer {query:?}")),
+    ///         }
+    ///     });
+    /// block_on(future).unwrap(); // any executor would do
+    /// assert_eq!(output, "Alice is 20 years old");
+    /// # }
+    /// ```
+    pub async fn write_to_async<
+        Output,
+        Segment,
+        ParseError,
+        RenderOutput,
+        RenderError,
+        QueryOutput,
+        QueryError,
+        QueryFuture,
+        Respond,
+    >(
+        self,
+        output: &mut Output,
+        mut respond: Respond,
+    ) -> Result<(), TemplateApplicationError<ParseError, RenderError, fmt::Error>>
+    where
+        Output: fmt::Write,
+        SegmentResultIntoIter::Item: Into<Result<Segment, ParseError>>,
+        RenderOutput: fmt::Display,
+        Segment: AsyncRender<Respond, RenderOutput, RenderError>,
+        Respond: FnMut(Query) -> QueryFuture,
+        QueryFuture: Future<Output = Result<QueryOutput, QueryError>>,
+    {
+        for segment in self.iter {
+            let response = segment
+                .into()
+                .map_err(TemplateApplicationError::Parse)?
+                .render_async(&mut respond)
+                .await
+                .map_err(TemplateApplicationError::Query)?;
+            write!(output, "{response}").map_err(TemplateApplicationError::Write)?;
+        }
+
+        Ok(())
+    }
+
+    /// List the queries of the template without sending them to a responder.
+    ///
+    /// Parsing stops after the first parse error, which is yielded as the last item.
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "std"))] fn main() {}
+    /// # #[cfg(feature = "std")] fn main() {
+    /// # use pretty_assertions::assert_eq;
+    /// let system = lazy_template::simple_curly_braces();
+    /// let queries: Vec<_> = system
+    ///     .lazy_parse("{name} is {age} years old } {descriptor}")
+    ///     .queries()
+    ///     .map(|query| query.map_err(|error| error.to_string()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     queries,
+    ///     [
+    ///         Ok("name"),
+    ///         Ok("age"),
+    ///         Err("Failed to parse query: Unexpected token \"}\"".to_string()),
+    ///     ],
+    /// );
+    /// # }
+    /// ```
+    pub fn queries<Segment, ParseError>(
+        self,
+    ) -> impl Iterator<Item = Result<Segment::Query, ParseError>>
+    where
+        SegmentResultIntoIter::Item: Into<Result<Segment, ParseError>>,
+        Segment: GetQuery,
+    {
+        self.iter
+            .into_iter()
+            .scan(false, |failed, segment| {
+                if *failed {
+                    return None;
+                }
+                let segment: Result<Segment, ParseError> = segment.into();
+                *failed = segment.is_err();
+                Some(segment)
+            })
+            .filter_map(|segment| match segment {
+                Ok(segment) => segment.query().map(Ok),
+                Err(error) => Some(Err(error)),
+            })
+    }
+
     /// Apply the template, and send the resulting segment outputs to `handle_query_output`.
     fn apply<
         Segment,
         ParseError,
         RenderOutput,
+        RenderError,
         QueryOutput,
         QueryError,
         Respond,
@@ -75,11 +202,11 @@ where
         self,
         mut respond: Respond,
         mut handle_query_output: HandleSegmentOutput,
-    ) -> Result<(), TemplateApplicationError<ParseError, QueryError, Infallible>>
+    ) -> Result<(), TemplateApplicationError<ParseError, RenderError, Infallible>>
     where
         SegmentResultIntoIter::Item: Into<Result<Segment, ParseError>>,
         HandleSegmentOutput: FnMut(RenderOutput),
-        Segment: Render<Respond, RenderOutput, QueryError>,
+        Segment: Render<Respond, RenderOutput, RenderError>,
         Respond: FnMut(Query) -> Result<QueryOutput, QueryError>,
     {
         for segment in self.iter {
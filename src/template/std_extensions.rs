@@ -1,23 +1,31 @@
 #![cfg(feature = "std")]
 use super::{Template, TemplateApplicationError};
-use crate::Render;
-use core::fmt;
+use crate::{AsyncRender, GetQuery, Render};
+use core::{fmt, future::Future, hash::Hash};
 use pipe_trait::Pipe;
-use std::io;
+use std::{collections::HashSet, io};
 
 impl<SegmentResultIntoIter, Query> Template<SegmentResultIntoIter, Query>
 where
     SegmentResultIntoIter: IntoIterator,
 {
     /// Apply the template, and join the resulting segment outputs together into a [`String`].
-    pub fn to_string<Segment, ParseError, RenderOutput, QueryOutput, QueryError, Respond>(
+    pub fn to_string<
+        Segment,
+        ParseError,
+        RenderOutput,
+        RenderError,
+        QueryOutput,
+        QueryError,
+        Respond,
+    >(
         self,
         respond: Respond,
-    ) -> Result<String, TemplateApplicationError<ParseError, QueryError, fmt::Error>>
+    ) -> Result<String, TemplateApplicationError<ParseError, RenderError, fmt::Error>>
     where
         SegmentResultIntoIter::Item: Into<Result<Segment, ParseError>>,
         RenderOutput: fmt::Display,
-        Segment: Render<Respond, RenderOutput, QueryError>,
+        Segment: Render<Respond, RenderOutput, RenderError>,
         Respond: FnMut(Query) -> Result<QueryOutput, QueryError>,
     {
         let mut buf = String::new();
@@ -25,18 +33,54 @@ where
         Ok(buf)
     }
 
+    /// Apply the template with a respond function that returns [futures](Future), and join the resulting segment
+    /// outputs together into a [`String`].
+    pub async fn to_string_async<
+        Segment,
+        ParseError,
+        RenderOutput,
+        RenderError,
+        QueryOutput,
+        QueryError,
+        QueryFuture,
+        Respond,
+    >(
+        self,
+        respond: Respond,
+    ) -> Result<String, TemplateApplicationError<ParseError, RenderError, fmt::Error>>
+    where
+        SegmentResultIntoIter::Item: Into<Result<Segment, ParseError>>,
+        RenderOutput: fmt::Display,
+        Segment: AsyncRender<Respond, RenderOutput, RenderError>,
+        Respond: FnMut(Query) -> QueryFuture,
+        QueryFuture: Future<Output = Result<QueryOutput, QueryError>>,
+    {
+        let mut buf = String::new();
+        self.write_to_async(&mut buf, respond).await?;
+        Ok(buf)
+    }
+
     /// Apply the template, and write the resulting segment outputs that implement [`fmt::Display`] to a
     /// writer that implements [`io::Write`].
-    pub fn to_writer<Writer, Segment, ParseError, RenderOutput, QueryOutput, QueryError, Respond>(
+    pub fn to_writer<
+        Writer,
+        Segment,
+        ParseError,
+        RenderOutput,
+        RenderError,
+        QueryOutput,
+        QueryError,
+        Respond,
+    >(
         self,
         writer: &mut Writer,
         respond: Respond,
-    ) -> Result<(), TemplateApplicationError<ParseError, QueryError, io::Error>>
+    ) -> Result<(), TemplateApplicationError<ParseError, RenderError, io::Error>>
     where
         Writer: io::Write,
         SegmentResultIntoIter::Item: Into<Result<Segment, ParseError>>,
         RenderOutput: fmt::Display,
-        Segment: Render<Respond, RenderOutput, QueryError>,
+        Segment: Render<Respond, RenderOutput, RenderError>,
         Respond: FnMut(Query) -> Result<QueryOutput, QueryError>,
     {
         let mut write_error = None;
@@ -56,4 +100,32 @@ where
 
         Ok(())
     }
+
+    /// List the distinct queries of the template in the order of their first appearances.
+    ///
+    /// ```
+    /// # use pretty_assertions::assert_eq;
+    /// let system = lazy_template::simple_curly_braces();
+    /// let queries = system
+    ///     .lazy_parse("{name} and {friend}, {friend} and {name}")
+    ///     .unique_queries()
+    ///     .unwrap();
+    /// assert_eq!(queries, ["name", "friend"]);
+    /// ```
+    pub fn unique_queries<Segment, ParseError>(self) -> Result<Vec<Segment::Query>, ParseError>
+    where
+        SegmentResultIntoIter::Item: Into<Result<Segment, ParseError>>,
+        Segment: GetQuery,
+        Segment::Query: Eq + Hash + Clone,
+    {
+        let mut queries = Vec::new();
+        let mut seen = HashSet::new();
+        for query in self.queries() {
+            let query = query?;
+            if seen.insert(query.clone()) {
+                queries.push(query);
+            }
+        }
+        Ok(queries)
+    }
 }
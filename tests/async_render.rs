@@ -0,0 +1,168 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{self, DefaultQueryParser, NestedQueryParser, Parser, SimpleEscapeParser},
+    simple_curly_braces, IntoTemplateSystem, TemplateApplicationError,
+};
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use std::{
+    borrow::Cow,
+    future::Future,
+    pin::{pin, Pin},
+    sync::Arc,
+    task::{Context, Poll, Wake},
+    thread::{self, Thread},
+};
+
+/// Minimal executor which parks the current thread until the future is woken.
+fn block_on<Fut: Future>(future: Fut) -> Fut::Output {
+    struct ThreadWaker(Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// Future that is pending once before resolving, like a lookup that has to wait for I/O.
+struct Delayed<Value> {
+    value: Option<Value>,
+    polled: bool,
+}
+
+impl<Value: Unpin> Future for Delayed<Value> {
+    type Output = Value;
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Value> {
+        if !self.polled {
+            self.polled = true;
+            context.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        self.value
+            .take()
+            .expect("polled after completion")
+            .pipe(Poll::Ready)
+    }
+}
+
+fn delayed<Value>(value: Value) -> Delayed<Value> {
+    Delayed {
+        value: Some(value),
+        polled: false,
+    }
+}
+
+fn respond(query: &str) -> Delayed<Result<&'static str, String>> {
+    delayed(match query {
+        "name" => Ok("Alice"),
+        "age" => Ok("20"),
+        _ => Err(format!("Can't answer {query:?}")),
+    })
+}
+
+#[test]
+fn lazy_template_to_string_async() {
+    let system = simple_curly_braces();
+    let output = system
+        .lazy_parse("{name} is {age} years old \\{age\\}")
+        .to_string_async(respond)
+        .pipe(block_on)
+        .unwrap();
+    assert_eq!(output, "Alice is 20 years old {age}");
+}
+
+#[test]
+fn parsed_template_to_string_async() {
+    let system = simple_curly_braces();
+    let parsed_template = system.eager_parse::<Vec<_>>("Hello, {name}!").unwrap();
+    let output = parsed_template
+        .to_template()
+        .to_string_async(|query| async move { respond(query).await })
+        .pipe(block_on)
+        .unwrap();
+    assert_eq!(output, "Hello, Alice!");
+}
+
+#[test]
+fn write_to_async_partial_output() {
+    let system = simple_curly_braces();
+    let mut output = String::new();
+    let error = system
+        .lazy_parse("{name} is {age} years } old")
+        .write_to_async(&mut output, respond)
+        .pipe(block_on)
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Parse(enclosed::ParseError::ParseQuery(
+            enclosed::simple_query::ParseError::UnexpectedToken(token, _)
+        )) if token == "}",
+    ));
+    assert_eq!(output, "Alice is 20 years ");
+}
+
+#[test]
+fn query_error() {
+    let system = simple_curly_braces();
+    let error = system
+        .lazy_parse("{name} is {descriptor}")
+        .to_string_async(respond)
+        .pipe(block_on)
+        .unwrap_err();
+    dbg!(&error);
+    let TemplateApplicationError::Query(error) = error else {
+        panic!("expecting a query error");
+    };
+    assert_eq!(error, "Can't answer \"descriptor\"");
+}
+
+#[test]
+fn default_query() {
+    let output = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(DefaultQueryParser)
+        .into_template_system::<&str>()
+        .lazy_parse(r#"{name} is a {descriptor ?? "person"}"#)
+        .to_string_async(respond)
+        .pipe(block_on)
+        .unwrap();
+    assert_eq!(output, "Alice is a person");
+}
+
+#[test]
+fn nested_query() {
+    let system = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(NestedQueryParser)
+        .into_template_system::<Cow<str>>();
+    let mut queries = Vec::new();
+    let output = system
+        .lazy_parse("{greeting}, {name:{lang:{country}}}!")
+        .to_string_async(|query| {
+            queries.push(query.to_string());
+            match query.as_ref() {
+                "greeting" => Ok("Hello"),
+                "country" => Ok("vn"),
+                "lang:vn" => Ok("vi"),
+                "name:vi" => Ok("Khải"),
+                _ => Err(format!("Can't answer {query:?}")),
+            }
+            .pipe(delayed)
+        })
+        .pipe(block_on)
+        .unwrap();
+    dbg!(&output);
+    assert_eq!(output, "Hello, Khải!");
+    assert_eq!(queries, ["greeting", "country", "lang:vn", "name:vi"]);
+}
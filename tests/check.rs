@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{self, simple_query},
+    simple_curly_braces, Diagnostic, GetSpan,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn no_diagnostics() {
+    let system = simple_curly_braces();
+    let diagnostics: Vec<_> = system.check("Hello, {name}! You are {age}.", |_| true);
+    dbg!(&diagnostics);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn all_parse_errors() {
+    let system = simple_curly_braces();
+    let diagnostics: Vec<_> = system.check("a } b\n\\z {c", |_| true);
+    dbg!(&diagnostics);
+    let received: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| format!("{}: {diagnostic}", diagnostic.span()))
+        .collect();
+    assert_eq!(
+        received,
+        [
+            "1:3: Failed to parse query: Unexpected token \"}\"",
+            "2:2: Failed to escape: Unsupported escape code 'z'",
+            "2:6: Failed to parse query: Unexpected end of input",
+        ],
+    );
+}
+
+#[test]
+fn disallowed_queries() {
+    let system = simple_curly_braces();
+    let diagnostics: Vec<_> = system.check("{name} {password} {age} {token}", |query| {
+        ["name", "age"].contains(query)
+    });
+    dbg!(&diagnostics);
+    let received: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| match diagnostic {
+            Diagnostic::DisallowedQuery(query, span) => {
+                (*query, span.start.column, span.end.column)
+            }
+            Diagnostic::Parse(error) => panic!("Unexpected parse error: {error}"),
+        })
+        .collect();
+    assert_eq!(received, [("password", 8, 18), ("token", 25, 32)]);
+}
+
+#[test]
+fn mixed_diagnostics() {
+    let system = simple_curly_braces();
+    let diagnostics: Vec<_> = system.check("{name} } {secret}", |query| *query == "name");
+    dbg!(&diagnostics);
+    assert!(matches!(
+        diagnostics.as_slice(),
+        [
+            Diagnostic::Parse(enclosed::ParseError::ParseQuery(
+                simple_query::ParseError::UnexpectedToken(token, _),
+            )),
+            Diagnostic::DisallowedQuery("secret", _),
+        ] if token == "}",
+    ));
+    assert_eq!(
+        diagnostics[1].to_string(),
+        "Query \"secret\" is not allowed"
+    );
+}
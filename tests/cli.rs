@@ -0,0 +1,269 @@
+#![cfg(feature = "cli")]
+use pretty_assertions::assert_eq;
+use std::{
+    env, fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+fn lazy_template(args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_lazy-template"));
+    command.args(args);
+    command
+}
+
+fn run(args: &[&str]) -> Output {
+    lazy_template(args).output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+fn temp_file(name: &str, content: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("lazy-template-test-cli-{name}"));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn positional_and_vars() {
+    let output = run(&[
+        "render",
+        "--template",
+        "{greeting}, {}! I am {}.",
+        "--var",
+        "greeting=Hello",
+        "Bob",
+        "Alice",
+    ]);
+    dbg!(&output);
+    assert_eq!(stdout(&output), "Hello, Bob! I am Alice.");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn env_and_file() {
+    let path = temp_file("env-and-file.txt", "file content");
+    let template = format!(
+        "{{env:LAZY_TEMPLATE_TEST_CLI}} and {{file:{}}}",
+        path.display()
+    );
+    let output = lazy_template(&["render", "--template", &template])
+        .env("LAZY_TEMPLATE_TEST_CLI", "env value")
+        .output()
+        .unwrap();
+    dbg!(&output);
+    assert_eq!(stdout(&output), "env value and file content");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn data_files() {
+    let json = temp_file(
+        "data.json",
+        r#"{ "user": { "name": "Alice", "tags": ["admin", "dev"] }, "age": 20 }"#,
+    );
+    let toml = temp_file("data.toml", "age = 21\n[server]\nport = 8080\n");
+    let output = run(&[
+        "render",
+        "--template",
+        "{user.name} ({age}) is {user.tags.0} at port {server.port}, {user.tags.1} at {host}",
+        "--data",
+        json.to_str().unwrap(),
+        "--data",
+        toml.to_str().unwrap(),
+        "--var",
+        "host=localhost",
+    ]);
+    dbg!(&output);
+    assert_eq!(
+        stdout(&output),
+        "Alice (21) is admin at port 8080, dev at localhost",
+    );
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn template_file() {
+    let path = temp_file("template-file.txt", "Hello, {name}!\n");
+    let output = run(&[
+        "render",
+        "--template-file",
+        path.to_str().unwrap(),
+        "--var",
+        "name=Alice",
+    ]);
+    dbg!(&output);
+    assert_eq!(stdout(&output), "Hello, Alice!\n");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn stdin_and_delimiters() {
+    let mut child = lazy_template(&[
+        "render",
+        "--open",
+        "<<",
+        "--close",
+        ">>",
+        "--var",
+        "name=Alice",
+    ])
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .spawn()
+    .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"Hello, <<name>> {name}!")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    dbg!(&output);
+    assert_eq!(stdout(&output), "Hello, Alice {name}!");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn read_failure() {
+    let output = run(&["render", "--template-file", "this-file-does-not-exist"]);
+    dbg!(&output);
+    assert_eq!(stdout(&output), "");
+    assert!(stderr(&output).starts_with("error: Failed to read this-file-does-not-exist: "));
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn invalid_arguments() {
+    let output = run(&["render", "--template", "", "--var", "no-equal-sign"]);
+    dbg!(&output);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn parse_failure() {
+    let output = run(&["render", "--template", "{a} }\n{b"]);
+    dbg!(&output);
+    assert_eq!(stdout(&output), "");
+    assert_eq!(
+        stderr(&output),
+        [
+            "error: <template>:1:5: Failed to parse query: Unexpected token \"}\"\n",
+            "error: <template>:2:3: Failed to parse query: Unexpected end of input\n",
+        ]
+        .concat(),
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn query_failure() {
+    let output = run(&["render", "--template", "{} and {}", "Alice"]);
+    dbg!(&output);
+    assert_eq!(stdout(&output), "");
+    assert_eq!(
+        stderr(&output),
+        "error: Positional argument at 1 is not provided\n",
+    );
+    assert_eq!(output.status.code(), Some(4));
+
+    let output = run(&["render", "--template", "{name}"]);
+    dbg!(&output);
+    assert_eq!(stderr(&output), "error: Unknown query \"name\"\n");
+    assert_eq!(output.status.code(), Some(4));
+}
+
+#[test]
+fn check_syntax() {
+    let valid = temp_file("check-syntax-valid.txt", "Hello, {name}!\n");
+    let invalid = temp_file("check-syntax-invalid.txt", "Hello, {name}}!\n{age");
+    let output = run(&["check", valid.to_str().unwrap(), invalid.to_str().unwrap()]);
+    dbg!(&output);
+    let invalid = invalid.display();
+    assert_eq!(
+        stdout(&output),
+        [
+            format!("{invalid}:1:14: Failed to parse query: Unexpected token \"}}\"\n"),
+            format!("{invalid}:2:5: Failed to parse query: Unexpected end of input\n"),
+        ]
+        .concat(),
+    );
+    assert_eq!(
+        stderr(&output),
+        "error: Found 2 parse error(s) and 0 disallowed query(ies)\n",
+    );
+    assert_eq!(output.status.code(), Some(3));
+
+    let output = run(&["check", valid.to_str().unwrap()]);
+    dbg!(&output);
+    assert_eq!(stdout(&output), "");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn check_allow_list() {
+    let template = temp_file("check-allow-list.txt", "{name} {env:HOME} {password} {age}");
+    let allow_file = temp_file("check-allow-list-allowed.txt", "age\n\nenv:*\n");
+    let output = run(&[
+        "check",
+        "--allow",
+        "name",
+        "--allow-file",
+        allow_file.to_str().unwrap(),
+        template.to_str().unwrap(),
+    ]);
+    dbg!(&output);
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "{}:1:19: Query \"password\" is not allowed\n",
+            template.display(),
+        ),
+    );
+    assert_eq!(output.status.code(), Some(4));
+}
+
+#[test]
+fn check_json() {
+    let template = temp_file("check-json.txt", "{name} }\n{password}");
+    let output = run(&[
+        "check",
+        "--allow",
+        "name",
+        "--format",
+        "json",
+        template.to_str().unwrap(),
+    ]);
+    dbg!(&output);
+    let diagnostics: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let file = template.display().to_string();
+    assert_eq!(
+        diagnostics,
+        serde_json::json!([
+            {
+                "file": file,
+                "kind": "parse",
+                "message": "Failed to parse query: Unexpected token \"}\"",
+                "query": null,
+                "start": { "line": 1, "column": 8, "offset": 7 },
+                "end": { "line": 1, "column": 9, "offset": 8 },
+            },
+            {
+                "file": file,
+                "kind": "disallowed-query",
+                "message": "Query \"password\" is not allowed",
+                "query": "password",
+                "start": { "line": 2, "column": 1, "offset": 9 },
+                "end": { "line": 2, "column": 11, "offset": 19 },
+            },
+        ]),
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    responders::{self, PrefixError},
+    simple_curly_braces, ResponderExt, TemplateApplicationError, UnknownQuery,
+};
+use pretty_assertions::assert_eq;
+use std::{cell::RefCell, collections::BTreeMap};
+
+#[test]
+fn or_else() {
+    let first = BTreeMap::from([("a", "first a")]);
+    let second = BTreeMap::from([("a", "second a"), ("b", "second b")]);
+    let output = simple_curly_braces()
+        .lazy_parse("{a}, {b}")
+        .to_string(responders::from_map(&first).or_else(responders::from_map(&second)))
+        .unwrap();
+    assert_eq!(output, "first a, second b");
+}
+
+#[test]
+fn or_else_error() {
+    let first = BTreeMap::from([("a", "first a")]);
+    let error = simple_curly_braces()
+        .lazy_parse("{a}, {b}")
+        .to_string(
+            responders::from_map(&first)
+                .or_else(|query: &str| Err::<&&str, _>(format!("{query} is unknown"))),
+        )
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(message) if message == "b is unknown",
+    ));
+}
+
+#[test]
+fn or_else_skip_next() {
+    let calls = RefCell::new(Vec::new());
+    let first = BTreeMap::from([("a", "first a")]);
+    let output = simple_curly_braces()
+        .lazy_parse("{a}, {b}")
+        .to_string(responders::from_map(&first).or_else(|query: &str| {
+            calls.borrow_mut().push(query.to_string());
+            Ok::<_, UnknownQuery>(&"next")
+        }))
+        .unwrap();
+    assert_eq!(output, "first a, next");
+    assert_eq!(calls.borrow().as_slice(), ["b"]);
+}
+
+#[test]
+fn route_prefix() {
+    let local = BTreeMap::from([("name", "Alice")]);
+    let secrets = BTreeMap::from([("name", "hunter2")]);
+    let config = BTreeMap::from([("name", "production")]);
+    let output = simple_curly_braces()
+        .lazy_parse("{name} {secret:name} {cfg:name}")
+        .to_string(
+            responders::from_map(&local)
+                .route_prefix("secret:", responders::from_map(&secrets))
+                .route_prefix("cfg:", responders::from_map(&config)),
+        )
+        .unwrap();
+    assert_eq!(output, "Alice hunter2 production");
+}
+
+#[test]
+fn route_prefix_error() {
+    let local = BTreeMap::from([("name", "Alice")]);
+    let secrets = BTreeMap::from([("token", "hunter2")]);
+    let error = simple_curly_braces()
+        .lazy_parse("{name} {secret:name}")
+        .to_string(
+            responders::from_map(&local).route_prefix("secret:", responders::from_map(&secrets)),
+        )
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(error.to_string(), r#"Unknown query "name""#);
+}
+
+#[test]
+fn map_output() {
+    let output = simple_curly_braces()
+        .lazy_parse("{} + {} = {}")
+        .to_string(responders::positional([1, 2, 3]).map_output(|x| x * 10))
+        .unwrap();
+    assert_eq!(output, "10 + 20 = 30");
+}
+
+#[test]
+fn map_err() {
+    let error = simple_curly_braces()
+        .lazy_parse("{env:LAZY_TEMPLATE_TEST_COMBINATORS_MISSING}")
+        .to_string(
+            responders::strip_prefix("env:", responders::from_env()).map_err(|error| match error {
+                PrefixError::Mismatch(error) => error.query,
+                PrefixError::Respond(error) => error.name,
+            }),
+        )
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(name) if name == "LAZY_TEMPLATE_TEST_COMBINATORS_MISSING",
+    ));
+}
+
+#[test]
+fn with_default() {
+    let map = BTreeMap::from([("name", "Alice")]);
+    let output = simple_curly_braces()
+        .lazy_parse("{name} is {age} years old")
+        .to_string(responders::from_map(&map).with_default(&"unknown"))
+        .unwrap();
+    assert_eq!(output, "Alice is unknown years old");
+}
+
+#[test]
+fn chain() {
+    std::env::set_var("LAZY_TEMPLATE_TEST_COMBINATORS_CHAIN", "from env");
+    let map = BTreeMap::from([("local", "from map".to_string())]);
+    let output = simple_curly_braces()
+        .lazy_parse(
+            "{local}, {LAZY_TEMPLATE_TEST_COMBINATORS_CHAIN}, {LAZY_TEMPLATE_TEST_COMBINATORS_UNDEFINED}",
+        )
+        .to_string(
+            responders::from_map(&map)
+                .map_output(String::clone)
+                .or_else(responders::from_env())
+                .with_default("from default".to_string()),
+        )
+        .unwrap();
+    assert_eq!(output, "from map, from env, from default");
+}
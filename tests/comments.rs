@@ -0,0 +1,179 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{
+        self, BlockParser, CommentConfig, Parser, ParserConfig, Segment, SimpleEscapeParser,
+        SimpleQueryParser,
+    },
+    GetSpan, IntoTemplateSystem,
+};
+use pretty_assertions::assert_eq;
+use std::cell::RefCell;
+
+type EnclosedParser = enclosed::Parser<SimpleEscapeParser, SimpleQueryParser>;
+
+fn parser() -> EnclosedParser {
+    Parser::curly_braces()
+        .with_config(ParserConfig::curly_braces().with_comment(CommentConfig::hash_curly_braces()))
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(SimpleQueryParser)
+}
+
+fn respond(query: &str) -> Result<&'static str, String> {
+    match query {
+        "name" => Ok("Alice"),
+        "age" => Ok("20"),
+        _ => Err(format!("Can't answer {query:?}")),
+    }
+}
+
+fn render(template: &str) -> Result<String, String> {
+    parser()
+        .into_template_system::<&str>()
+        .lazy_parse(template)
+        .to_string(respond)
+        .map_err(|error| error.to_string())
+}
+
+#[test]
+fn remove_comments_from_output() {
+    let actual = render("{# header #}{name} is{# {unknown} #} {age}{#a#}{#b#}.{# footer #}");
+    assert_eq!(actual.as_deref(), Ok("Alice is 20."));
+    assert_eq!(render("{# only a comment #}").as_deref(), Ok(""));
+    assert_eq!(render("").as_deref(), Ok(""));
+}
+
+#[test]
+fn comments_never_reach_responder() {
+    let queries = RefCell::new(Vec::new());
+    let system = parser().into_template_system::<&str>();
+    let output = system
+        .lazy_parse("{name}{# {age} #}")
+        .to_string(|query| {
+            queries.borrow_mut().push(query);
+            respond(query)
+        })
+        .unwrap();
+    assert_eq!(output, "Alice");
+    assert_eq!(queries.borrow().as_slice(), ["name"]);
+}
+
+#[test]
+fn comments_are_disabled_by_default() {
+    let output = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(SimpleQueryParser)
+        .into_template_system::<&str>()
+        .lazy_parse("{# name #}")
+        .to_string(|query| Ok::<_, String>(format!("[{query}]")))
+        .unwrap();
+    assert_eq!(output, "[# name #]");
+}
+
+#[test]
+fn escape_comment_delimiters() {
+    let actual = render(r"\{# {name} #\}");
+    assert_eq!(actual.as_deref(), Ok("{# Alice #}"));
+}
+
+#[test]
+fn preserve_segment_spans() {
+    let system = parser().into_template_system::<&str>();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>("{# greeting #}Hello,\n{#x#}{name}")
+        .unwrap();
+    let spans: Vec<_> = parsed_template
+        .segments()
+        .map(|segment| segment.span().to_string())
+        .collect();
+    assert_eq!(spans, ["1:15", "2:6"]);
+}
+
+#[test]
+fn trailing_comments_produce_no_segment() {
+    let system = parser().into_template_system::<&str>();
+    let segments = |template| -> Vec<_> {
+        system
+            .eager_parse::<Vec<_>>(template)
+            .unwrap()
+            .segments()
+            .map(|segment| match *segment {
+                Segment::Text(text, span) => format!("Text({text:?}) at {span}"),
+                _ => format!("{segment:?}"),
+            })
+            .collect()
+    };
+    assert_eq!(segments("Hi{# trailing #}"), [r#"Text("Hi") at 1:1"#]);
+    assert_eq!(segments("{# only #}"), Vec::<String>::new());
+    assert_eq!(segments("{#a#}{#b#}"), Vec::<String>::new());
+
+    let (parsed_template, errors) = system.recovering_parse::<Vec<_>, Vec<_>>(r"Hi{#a#}\z{#b#}");
+    dbg!(&parsed_template, &errors);
+    assert_eq!(parsed_template.segments().count(), 1);
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn list_comments() {
+    let parser = parser();
+    let comments: Vec<_> = parser
+        .comments("{# greeting #}Hello, {name}!\n{#TODO#}{#} {age}")
+        .map(|comment| {
+            (
+                comment.text,
+                comment.span.start.line,
+                comment.span.start.column,
+            )
+        })
+        .collect();
+    assert_eq!(comments, [(" greeting ", 1, 1), ("TODO", 2, 1)]);
+}
+
+#[test]
+fn unclosed_comment() {
+    let system = parser().into_template_system::<&str>();
+    let error = system.eager_parse::<Vec<_>>("{name} {# note").unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        enclosed::ParseError::UnexpectedEndOfInput(_)
+    ));
+    assert_eq!(
+        format!("{}: {error}", error.span()),
+        "1:15: Unexpected end of input"
+    );
+}
+
+#[test]
+fn custom_comment_delimiters() {
+    let config = ParserConfig::double_curly_braces()
+        .with_comment(CommentConfig::new("<!--", "-->").unwrap());
+    let output = parser()
+        .with_config(config)
+        .into_template_system::<&str>()
+        .lazy_parse("<!-- {{age}} -->{{name}}<!---->!")
+        .to_string(respond)
+        .unwrap();
+    assert_eq!(output, "Alice!");
+}
+
+#[test]
+fn comments_in_sections() {
+    let parser = BlockParser::new(parser());
+    let template = "{# intro #}{#if name}{# yes #}{name}{#else}{# no #}nobody{/if}{# end #}";
+    let output = parser
+        .clone()
+        .into_template_system::<&str>()
+        .lazy_parse(template)
+        .to_string(respond)
+        .unwrap();
+    assert_eq!(output, "Alice");
+    let comments: Vec<_> = parser
+        .comments(template)
+        .map(|comment| comment.text)
+        .collect();
+    assert_eq!(comments, [" intro ", " yes ", " no ", " end "]);
+    let system = parser.into_template_system::<&str>();
+    let parsed_template = system.eager_parse::<Vec<_>>(template).unwrap();
+    dbg!(&parsed_template);
+    assert_eq!(parsed_template.segments().count(), 1);
+}
@@ -0,0 +1,139 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{FilterRegistry, FilteredQueryParser, Parser, SimpleEscapeParser},
+    simple_curly_braces, IntoTemplateSystem, TemplateApplicationError,
+};
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use std::{
+    cell::Cell,
+    future::{poll_fn, Future},
+    pin::pin,
+    sync::{Condvar, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+    time::Duration,
+};
+
+/// Minimal executor which gives up after a number of polls instead of hanging forever.
+fn block_on<Fut: Future>(future: Fut) -> Fut::Output {
+    let mut context = Context::from_waker(Waker::noop());
+    let mut future = pin!(future);
+    for _ in 0..1000 {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+    panic!("the future did not complete");
+}
+
+fn respond(query: &str) -> Result<String, String> {
+    thread::sleep(Duration::from_millis(5));
+    match query {
+        "fail" | "also fail" => Err(format!("Can't answer {query:?}")),
+        _ => Ok(query.to_uppercase()),
+    }
+}
+
+#[test]
+fn to_string_concurrent() {
+    let system = simple_curly_braces();
+    let template: String = (0..32).map(|index| format!("{{q{index}}} ")).collect();
+    let parsed_template = system.eager_parse::<Vec<_>>(&template).unwrap();
+    let actual = parsed_template.to_string_concurrent(respond).unwrap();
+    let expected: String = (0..32).map(|index| format!("Q{index} ")).collect();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn to_string_concurrent_sends_every_query_at_once() {
+    let system = simple_curly_braces();
+    let template: String = (0..32).map(|index| format!("{{q{index}}} ")).collect();
+    let parsed_template = system.eager_parse::<Vec<_>>(&template).unwrap();
+
+    // every query waits for all of them to be sent, which would time out if some were only sent after others returned
+    let sent = (Mutex::new(0), Condvar::new());
+    let output = parsed_template
+        .to_string_concurrent(|query| {
+            let (count, all_sent) = &sent;
+            let mut count = count.lock().unwrap();
+            *count += 1;
+            all_sent.notify_all();
+            let (_count, timeout) = all_sent
+                .wait_timeout_while(count, Duration::from_secs(10), |count| *count < 32)
+                .unwrap();
+            match timeout.timed_out() {
+                true => Err(format!("{query:?} was not sent together with the others")),
+                false => Ok(query.to_uppercase()),
+            }
+        })
+        .unwrap();
+    let expected: String = (0..32).map(|index| format!("Q{index} ")).collect();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn to_string_concurrent_first_error() {
+    let system = simple_curly_braces();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>("{a} {also fail} {b} {fail}")
+        .unwrap();
+    let error = parsed_template.to_string_concurrent(respond).unwrap_err();
+    dbg!(&error);
+    let TemplateApplicationError::Query(error) = error else {
+        panic!("expecting a query error");
+    };
+    assert_eq!(error, "Can't answer \"also fail\"");
+}
+
+#[test]
+fn to_string_concurrent_filtered_query() {
+    let registry = FilterRegistry::builtin();
+    let system = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(FilteredQueryParser::new(&registry))
+        .into_template_system::<&str>();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>("{alice | lower}, {bob | truncate(2)}")
+        .unwrap();
+    let output = parsed_template.to_string_concurrent(respond).unwrap();
+    assert_eq!(output, "alice, BO");
+}
+
+#[test]
+fn to_string_concurrent_async() {
+    let system = simple_curly_braces();
+    let parsed_template = system.eager_parse::<Vec<_>>("{a}, {b}, {c}!").unwrap();
+
+    // every query is pending until all of them have been sent, which would never happen if they were awaited one by one
+    let started = Cell::new(0);
+    let output = parsed_template
+        .to_string_concurrent_async(|query| {
+            started.set(started.get() + 1);
+            let started = &started;
+            poll_fn(move |_| match started.get() {
+                3 => Poll::Ready(Ok::<_, String>(query.to_uppercase())),
+                _ => Poll::Pending,
+            })
+        })
+        .pipe(block_on)
+        .unwrap();
+    assert_eq!(output, "A, B, C!");
+}
+
+#[test]
+fn to_string_concurrent_async_first_error() {
+    let system = simple_curly_braces();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>("{a} {also fail} {b} {fail}")
+        .unwrap();
+    let error = parsed_template
+        .to_string_concurrent_async(|query| async move { respond(query) })
+        .pipe(block_on)
+        .unwrap_err();
+    dbg!(&error);
+    let TemplateApplicationError::Query(error) = error else {
+        panic!("expecting a query error");
+    };
+    assert_eq!(error, "Can't answer \"also fail\"");
+}
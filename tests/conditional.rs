@@ -0,0 +1,266 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{
+        self, block, formatted_query, BlockParser, FormattedQueryParser, Parser, ParserConfig,
+        SimpleEscapeParser, SimpleQueryParser,
+    },
+    GetSpan, IntoTemplateSystem, TemplateApplicationError, TemplateSystem,
+};
+use pretty_assertions::assert_eq;
+use std::{cell::RefCell, fmt};
+
+type BlockTemplateSystem<'a> =
+    TemplateSystem<BlockParser<enclosed::Parser<SimpleEscapeParser, SimpleQueryParser>>, &'a str>;
+
+fn system<'a>() -> BlockTemplateSystem<'a> {
+    system_with_config(ParserConfig::curly_braces())
+}
+
+fn system_with_config<'a>(config: ParserConfig) -> BlockTemplateSystem<'a> {
+    let parser = Parser::curly_braces()
+        .with_config(config)
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(SimpleQueryParser);
+    BlockParser::new(parser).into_template_system()
+}
+
+fn respond(query: &str) -> Result<&'static str, String> {
+    match query {
+        "name" => Ok("Alice"),
+        "discount" => Ok("10%"),
+        "empty" => Ok(""),
+        "no" => Ok("false"),
+        "yes" => Ok("true"),
+        _ => Err(format!("Can't answer {query:?}")),
+    }
+}
+
+#[test]
+fn take_then_branch() {
+    let output = system()
+        .lazy_parse("Hi {name}.{#if discount} Save {discount}!{#else} No discount.{/if} Bye.")
+        .to_string(respond)
+        .unwrap();
+    assert_eq!(output, "Hi Alice. Save 10%! Bye.");
+}
+
+#[test]
+fn take_else_branch() {
+    let output = system()
+        .lazy_parse("{#if coupon}Use {coupon}.{#else}No coupon, {name}.{/if}")
+        .to_string(respond)
+        .unwrap();
+    assert_eq!(output, "No coupon, Alice.");
+}
+
+#[test]
+fn without_else_branch() {
+    let output = system()
+        .lazy_parse("[{#if coupon}Use {coupon}.{/if}][{#if name}Hello, {name}.{/if}]")
+        .to_string(respond)
+        .unwrap();
+    assert_eq!(output, "[][Hello, Alice.]");
+}
+
+#[test]
+fn truthiness() {
+    let output = system()
+        .lazy_parse(
+            "{#if empty}T{#else}F{/if} {#if no}T{#else}F{/if} {#if yes}T{#else}F{/if} {#if unknown}T{#else}F{/if}",
+        )
+        .to_string(respond)
+        .unwrap();
+    assert_eq!(output, "F F T F");
+}
+
+/// Response whose [`Display`](fmt::Display) implementation always fails.
+struct Unprintable;
+
+impl fmt::Display for Unprintable {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Err(fmt::Error)
+    }
+}
+
+impl block::SectionResponse for Unprintable {}
+
+#[test]
+fn unprintable_condition_is_falsy() {
+    let output = system()
+        .lazy_parse("{#if x}then{#else}else{/if}")
+        .to_string(|_| Ok::<_, String>(Unprintable))
+        .unwrap();
+    assert_eq!(output, "else");
+}
+
+#[test]
+fn format_error_in_section() {
+    let parser = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(FormattedQueryParser);
+    let system = BlockParser::new(parser).into_template_system::<&str>();
+    let error = system
+        .lazy_parse("{n:x}")
+        .to_string(|_| Ok::<_, String>("abc"))
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(block::RenderError::Segment(
+            formatted_query::RenderError::NotANumber(_),
+        )),
+    ));
+    let error = system
+        .lazy_parse("{#if n}{n:x}{/if}")
+        .to_string(|_| Ok::<_, String>("abc"))
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(block::RenderError::Segment(
+            formatted_query::RenderError::NotANumber(_),
+        )),
+    ));
+}
+
+#[test]
+fn nested_sections() {
+    let template = "{#if name}A{#if discount}B{#if coupon}C{#else}D{/if}E{/if}F{#else}G{/if}";
+    let output = system().lazy_parse(template).to_string(respond).unwrap();
+    assert_eq!(output, "ABDEF");
+}
+
+#[test]
+fn untaken_branch_queries_are_not_sent() {
+    let queries = RefCell::new(Vec::new());
+    let output = system()
+        .lazy_parse("{#if discount}{name}{#else}{secret}{/if}{#if coupon}{password}{/if}")
+        .to_string(|query| {
+            queries.borrow_mut().push(query.to_string());
+            respond(query)
+        })
+        .unwrap();
+    assert_eq!(output, "Alice");
+    assert_eq!(queries.borrow().as_slice(), ["discount", "name", "coupon"]);
+}
+
+#[test]
+fn query_error_in_taken_branch() {
+    let error = system()
+        .lazy_parse("{#if discount}{unknown}{/if}")
+        .to_string(respond)
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(block::RenderError::Segment(message))
+            if message == r#"Can't answer "unknown""#,
+    ));
+}
+
+#[test]
+fn eager_parse_tree() {
+    let system = system();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>("a{#if x}b{#else}c{/if}")
+        .unwrap();
+    let blocks: Vec<_> = parsed_template.segments().collect();
+    dbg!(&blocks);
+    let [block::Block::Segment(_), block::Block::If(conditional)] = blocks.as_slice() else {
+        panic!("Expected a text segment followed by a conditional section");
+    };
+    assert_eq!(conditional.condition, "x");
+    assert_eq!(conditional.then.len(), 1);
+    assert_eq!(conditional.otherwise.len(), 1);
+    assert_eq!(
+        (conditional.span.start.column, conditional.span.end.column),
+        (2, 23),
+    );
+}
+
+#[test]
+fn custom_delimiters() {
+    let output = system_with_config(ParserConfig::double_curly_braces())
+        .lazy_parse("{{#if name}}Hello, {{name}}{{#else}}Nobody{{/if}}")
+        .to_string(respond)
+        .unwrap();
+    assert_eq!(output, "Hello, Alice");
+}
+
+#[test]
+fn other_hash_queries_are_ordinary_queries() {
+    let output = system()
+        .lazy_parse("{#iffy} {#ifx}")
+        .to_string(|query| Ok::<_, String>(query.to_uppercase()))
+        .unwrap();
+    assert_eq!(output, "#IFFY #IFX");
+}
+
+fn parse_error(template: &str) -> String {
+    let system = system();
+    let error = system.eager_parse::<Vec<_>>(template).unwrap_err();
+    dbg!(&error);
+    format!("{}: {error}", error.span())
+}
+
+#[test]
+fn unclosed_section() {
+    assert_eq!(parse_error("a {#if x} b"), "1:3: Section is not closed");
+    assert_eq!(
+        parse_error("a {#if x} b {#else} c"),
+        "1:3: Section is not closed",
+    );
+}
+
+#[test]
+fn unexpected_tags() {
+    assert_eq!(parse_error("a {/if}"), r#"1:3: Unexpected tag "/if""#);
+    assert_eq!(
+        parse_error("a {#else} b"),
+        r##"1:3: Unexpected tag "#else""##
+    );
+    assert_eq!(
+        parse_error("{#if x}a{#else}b{#else}c{/if}"),
+        r##"1:17: Unexpected tag "#else""##,
+    );
+}
+
+#[test]
+fn missing_condition() {
+    assert_eq!(parse_error("{#if}a{/if}"), "1:1: Section has no condition");
+    assert_eq!(
+        parse_error("{#if  }a{/if}"),
+        "1:1: Section has no condition"
+    );
+}
+
+#[test]
+fn segment_error_in_section() {
+    assert_eq!(
+        parse_error("{#if x} } {/if}"),
+        r#"1:9: Failed to parse query: Unexpected token "}""#,
+    );
+}
+
+#[test]
+fn recovering_parse() {
+    let system = system();
+    let template = "a {/if}{#if}b{/if} {#if x}{y}}{#if z}{/if}{/if} {name}{#if w}c";
+    let (parsed_template, errors) = system.recovering_parse::<Vec<_>, Vec<_>>(template);
+    dbg!(&parsed_template, &errors);
+    let output = parsed_template.to_template().to_string(respond).unwrap();
+    assert_eq!(output, "a   Alicec");
+    let errors: Vec<_> = errors
+        .iter()
+        .map(|error| format!("{}: {error}", error.span()))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            r#"1:3: Unexpected tag "/if""#,
+            "1:8: Section has no condition",
+            r#"1:30: Failed to parse query: Unexpected token "}""#,
+            "1:55: Section is not closed",
+        ],
+    );
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{self, default_query, DefaultQueryParser, Parser, SimpleEscapeParser},
+    GetSpan, IntoTemplateSystem, TemplateApplicationError, TemplateSystem,
+};
+use pretty_assertions::assert_eq;
+
+type DefaultTemplateSystem<'a> =
+    TemplateSystem<enclosed::Parser<SimpleEscapeParser, DefaultQueryParser>, &'a str>;
+
+fn system<'a>() -> DefaultTemplateSystem<'a> {
+    Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(DefaultQueryParser)
+        .into_template_system()
+}
+
+fn respond(query: &str) -> Result<&'static str, String> {
+    match query {
+        "name" => Ok("Alice"),
+        _ => Err(format!("Can't answer {query:?}")),
+    }
+}
+
+#[test]
+fn fall_back_to_default_values() {
+    let actual = system()
+        .lazy_parse(
+            r#"{name ?? "anonymous"}, {title ?? "{no title}"}, {city:-Hà Nội}, {country:-}."#,
+        )
+        .to_string(respond)
+        .unwrap();
+    assert_eq!(actual, "Alice, {no title}, Hà Nội, .");
+}
+
+#[test]
+fn trim_unquoted_default_values() {
+    let system = system();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>(r#"{name :- anonymous} {title :-  } {city ?? " Hà Nội "}"#)
+        .unwrap();
+    let queries: Vec<_> = parsed_template
+        .queries()
+        .map(|query| (query.query, query.default))
+        .collect();
+    dbg!(&queries);
+    assert_eq!(
+        queries,
+        [
+            ("name", Some("anonymous")),
+            ("title", Some("")),
+            ("city", Some(" Hà Nội ")),
+        ],
+    );
+    let actual = parsed_template.to_template().to_string(respond).unwrap();
+    assert_eq!(actual, "Alice   Hà Nội ");
+}
+
+#[test]
+fn fail_without_default_values() {
+    let error = system()
+        .lazy_parse(r#"{name ?? "anonymous"} is {age}"#)
+        .to_string(respond)
+        .unwrap_err();
+    dbg!(&error);
+    assert!(
+        matches!(error, TemplateApplicationError::Query(ref message) if message == r#"Can't answer "age""#)
+    );
+}
+
+#[test]
+fn reject_invalid_default_values() {
+    let system = system();
+    let error = system
+        .eager_parse::<Vec<_>>("Hello, {name ?? anonymous}!")
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error.to_string(),
+        "Failed to parse query: Default value should be a string literal",
+    );
+    assert!(matches!(
+        error,
+        enclosed::ParseError::ParseQuery(default_query::ParseError::InvalidDefault(_)),
+    ));
+    assert_eq!(error.span().start.offset, 16);
+    assert_eq!(error.span().end.offset, 25);
+
+    let error = system
+        .eager_parse::<Vec<_>>(r#"Hello, {name ?? "anonymous" or "nobody"}!"#)
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error.to_string(),
+        "Failed to parse query: Default value should be a string literal",
+    );
+    assert_eq!(error.span().start.offset, 28);
+    assert_eq!(error.span().end.offset, 39);
+
+    let error = system
+        .eager_parse::<Vec<_>>(r#"Hello, {name ?? "anonymous"#)
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error.to_string(),
+        "Failed to parse query: Unexpected end of input",
+    );
+}
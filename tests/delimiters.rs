@@ -0,0 +1,108 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{
+        self, simple_query, ConfigError, Parser, ParserConfig, SimpleEscapeParser,
+        SimpleQueryParser,
+    },
+    IntoTemplateSystem,
+};
+use pretty_assertions::assert_eq;
+
+fn render(config: ParserConfig, template: &str) -> Result<String, String> {
+    Parser::curly_braces()
+        .with_config(config)
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(SimpleQueryParser)
+        .into_template_system::<&str>()
+        .lazy_parse(template)
+        .to_string(|query| match query.trim() {
+            "name" => Ok("Alice"),
+            "age" => Ok("20"),
+            other => Err(format!("{other} is undefined")),
+        })
+        .map_err(|error| error.to_string())
+}
+
+#[test]
+fn double_curly_braces() {
+    let actual = render(
+        ParserConfig::double_curly_braces(),
+        "{{ name }} is {{age}} {years} old",
+    );
+    assert_eq!(actual.as_deref(), Ok("Alice is 20 {years} old"));
+}
+
+#[test]
+fn dollar_curly_braces() {
+    let actual = render(
+        ParserConfig::dollar_curly_braces(),
+        r"${name} costs $5, {age\} is ${age}",
+    );
+    assert_eq!(actual.as_deref(), Ok("Alice costs $5, {age} is 20"));
+}
+
+#[test]
+fn custom_delimiters() {
+    let erb = ParserConfig::new("<%=", "%>").unwrap();
+    let actual = render(erb, "<%= name %> is <%=age%> years old");
+    assert_eq!(actual.as_deref(), Ok("Alice is 20 years old"));
+
+    let percent = ParserConfig::new("%", "%").unwrap();
+    let actual = render(percent, "%name% is %age% years old");
+    assert_eq!(actual.as_deref(), Ok("Alice is 20 years old"));
+}
+
+#[test]
+fn runtime_delimiters() {
+    let (open, close) = (String::from("[["), String::from("]]"));
+    let config = ParserConfig::new(open, close).unwrap();
+    assert_eq!(
+        (config.open_bracket(), config.close_bracket()),
+        ("[[", "]]")
+    );
+    let actual = render(config, r"[[name]] is [[age]] \[[years\]]");
+    assert_eq!(actual.as_deref(), Ok("Alice is 20 [[years]]"));
+}
+
+#[test]
+fn empty_delimiters() {
+    let error = ParserConfig::new("", "}").unwrap_err();
+    assert_eq!(error, ConfigError::EmptyOpenBracket);
+    assert_eq!(error.to_string(), "Opening delimiter must not be empty");
+    let error = ParserConfig::new("{", String::new()).unwrap_err();
+    assert_eq!(error, ConfigError::EmptyCloseBracket);
+    assert_eq!(error.to_string(), "Closing delimiter must not be empty");
+}
+
+#[test]
+fn escape_delimiters() {
+    let actual = render(
+        ParserConfig::double_curly_braces(),
+        r"\{{name\}} is {{name}}",
+    );
+    assert_eq!(actual.as_deref(), Ok("{{name}} is Alice"));
+
+    let actual = render(ParserConfig::dollar_curly_braces(), r"\${name\} is ${name}");
+    assert_eq!(actual.as_deref(), Ok("${name} is Alice"));
+}
+
+#[test]
+fn reject_unexpected_close_delimiter() {
+    let error = Parser::curly_braces()
+        .with_config(ParserConfig::double_curly_braces())
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(SimpleQueryParser)
+        .into_template_system::<&str>()
+        .eager_parse::<Vec<_>>("{{name}} }} {{age}}")
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error.to_string(),
+        r#"Failed to parse query: Unexpected token "}}""#,
+    );
+    assert!(matches!(
+        error,
+        enclosed::ParseError::ParseQuery(simple_query::ParseError::UnexpectedToken(token, _))
+            if token == "}}",
+    ));
+}
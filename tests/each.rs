@@ -0,0 +1,280 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{
+        self,
+        block::{self, Response},
+        formatted_query, BlockParser, FormattedQueryParser, Parser, SimpleEscapeParser,
+        SimpleQueryParser,
+    },
+    GetSpan, IntoTemplateSystem, RespondFields, TemplateApplicationError, TemplateSystem,
+    UnknownQuery,
+};
+use pretty_assertions::assert_eq;
+use std::{cell::RefCell, collections::BTreeMap, fmt};
+
+type BlockTemplateSystem<'a> =
+    TemplateSystem<BlockParser<enclosed::Parser<SimpleEscapeParser, SimpleQueryParser>>, &'a str>;
+
+fn system<'a>() -> BlockTemplateSystem<'a> {
+    let parser = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(SimpleQueryParser);
+    BlockParser::new(parser).into_template_system()
+}
+
+struct LineItem {
+    name: &'static str,
+    price: u32,
+    tags: Vec<BTreeMap<String, &'static str>>,
+}
+
+impl RespondFields for LineItem {
+    fn respond(&self, query: &str) -> Result<&dyn fmt::Display, UnknownQuery> {
+        match query {
+            "name" => Ok(&self.name),
+            "price" => Ok(&self.price),
+            _ => Err(UnknownQuery::new(query)),
+        }
+    }
+}
+
+fn line_items() -> Vec<LineItem> {
+    let tag = |name| BTreeMap::from([("tag".to_string(), name)]);
+    vec![
+        LineItem {
+            name: "Apple",
+            price: 3,
+            tags: vec![tag("fruit"), tag("red")],
+        },
+        LineItem {
+            name: "Bread",
+            price: 5,
+            tags: Vec::new(),
+        },
+    ]
+}
+
+fn respond<'r>(
+    items: &'r [LineItem],
+) -> impl FnMut(&str) -> Result<Response<&'static str, &'r dyn RespondFields>, String> {
+    move |query| match query {
+        "customer" => Ok(Response::Value("Alice")),
+        "currency" => Ok(Response::Value("$")),
+        "items" => Ok(Response::List(
+            items
+                .iter()
+                .map(|item| item as &dyn RespondFields)
+                .collect(),
+        )),
+        "nothing" => Ok(Response::List(Vec::new())),
+        _ => Err(format!("Can't answer {query:?}")),
+    }
+}
+
+#[test]
+fn render_body_per_item() {
+    let items = line_items();
+    let output = system()
+        .lazy_parse(
+            "Dear {customer}:\n{#each items}{@index}. {.name}: {currency}{.price}\n{/each}Bye.",
+        )
+        .to_string(respond(&items))
+        .unwrap();
+    assert_eq!(output, "Dear Alice:\n0. Apple: $3\n1. Bread: $5\nBye.");
+}
+
+#[test]
+fn render_else_branch_without_items() {
+    let items = line_items();
+    let output = system()
+        .lazy_parse(
+            "[{#each nothing}{.name}{#else}empty{/each}][{#each customer}{.name}{#else}not a list{/each}][{#each unknown}{.name}{#else}unknown{/each}]",
+        )
+        .to_string(respond(&items))
+        .unwrap();
+    assert_eq!(output, "[empty][not a list][unknown]");
+}
+
+#[test]
+fn item_queries_fall_back_to_responder() {
+    let queries = RefCell::new(Vec::new());
+    let items = line_items();
+    let mut respond = respond(&items);
+    let output = system()
+        .lazy_parse("{#each items}{.name}{.customer} {/each}{@index}")
+        .to_string(|query| {
+            queries.borrow_mut().push(query.to_string());
+            match query {
+                ".customer" => Ok(Response::Value("?")),
+                "@index" => Ok(Response::Value("!")),
+                _ => respond(query),
+            }
+        })
+        .unwrap();
+    assert_eq!(output, "Apple? Bread? !");
+    assert_eq!(
+        queries.borrow().as_slice(),
+        ["items", ".customer", ".customer", "@index"],
+    );
+}
+
+#[test]
+fn nested_loops() {
+    let items = line_items();
+    let mut respond = respond(&items);
+    let template = "{#each items}{.name}({#each tags}{@index}:{.tag}/{.name} {/each}) {/each}";
+    let output = system()
+        .lazy_parse(template)
+        .to_string(|query| match query {
+            "tags" => Ok(Response::List(
+                items[0]
+                    .tags
+                    .iter()
+                    .map(|tag| tag as &dyn RespondFields)
+                    .collect(),
+            )),
+            _ => respond(query),
+        })
+        .unwrap();
+    assert_eq!(
+        output,
+        "Apple(0:fruit/Apple 1:red/Apple ) Bread(0:fruit/Bread 1:red/Bread ) ",
+    );
+}
+
+#[test]
+fn conditions_inside_loops() {
+    let items = line_items();
+    let output = system()
+        .lazy_parse("{#each items}{.name}{#if .discount} (sale){/if}{#if .price} for {.price}{/if}, {/each}")
+        .to_string(respond(&items))
+        .unwrap();
+    assert_eq!(output, "Apple for 3, Bread for 5, ");
+}
+
+#[test]
+fn list_truthiness() {
+    let items = line_items();
+    let output = system()
+        .lazy_parse("{#if items}T{#else}F{/if} {#if nothing}T{#else}F{/if}")
+        .to_string(respond(&items))
+        .unwrap();
+    assert_eq!(output, "T F");
+}
+
+#[test]
+fn query_error_in_body() {
+    let items = line_items();
+    let error = system()
+        .lazy_parse("{#each items}{.name}{unknown}{/each}")
+        .to_string(respond(&items))
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(block::RenderError::Segment(message))
+            if message == r#"Can't answer "unknown""#,
+    ));
+}
+
+#[test]
+fn format_error_in_body() {
+    let parser = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(FormattedQueryParser);
+    let system = BlockParser::new(parser).into_template_system::<&str>();
+    let items = line_items();
+    let error = system
+        .lazy_parse("{#each items}{.name:x}{/each}")
+        .to_string(respond(&items))
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(block::RenderError::Segment(
+            formatted_query::RenderError::NotANumber(_),
+        )),
+    ));
+}
+
+/// Value whose [`Display`](fmt::Display) implementation always fails.
+struct Unprintable;
+
+impl fmt::Display for Unprintable {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Err(fmt::Error)
+    }
+}
+
+impl block::SectionResponse for Unprintable {}
+
+#[test]
+fn write_error_in_body() {
+    let item = BTreeMap::from([("name".to_string(), Unprintable)]);
+    let respond = |query: &str| match query {
+        "items" => Ok(Response::List(vec![&item])),
+        "unprintable" => Ok(Response::Value(Unprintable)),
+        _ => Err(format!("Can't answer {query:?}")),
+    };
+
+    let error = system()
+        .lazy_parse("{#each items}{.name}{/each}")
+        .to_string(respond)
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(block::RenderError::Write(fmt::Error)),
+    ));
+
+    let mut output = Vec::new();
+    let error = system()
+        .lazy_parse("[{#each items}{unprintable}{/each}]")
+        .to_writer(&mut output, respond)
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(block::RenderError::Write(fmt::Error)),
+    ));
+    assert_eq!(String::from_utf8(output).unwrap(), "[");
+}
+
+#[test]
+fn eager_parse_tree() {
+    let system = system();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>("a{#each x}b{#else}c{/each}")
+        .unwrap();
+    let blocks: Vec<_> = parsed_template.segments().collect();
+    dbg!(&blocks);
+    let [block::Block::Segment(_), block::Block::Each(each)] = blocks.as_slice() else {
+        panic!("Expected a text segment followed by a loop section");
+    };
+    assert_eq!(each.list, "x");
+    assert_eq!(each.body.len(), 1);
+    assert_eq!(each.otherwise.len(), 1);
+    assert_eq!((each.span.start.column, each.span.end.column), (2, 27));
+}
+
+fn parse_error(template: &str) -> String {
+    let system = system();
+    let error = system.eager_parse::<Vec<_>>(template).unwrap_err();
+    dbg!(&error);
+    format!("{}: {error}", error.span())
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(parse_error("a {#each x} b"), "1:3: Section is not closed");
+    assert_eq!(parse_error("{#each}a{/each}"), "1:1: Section has no list");
+    assert_eq!(parse_error("a {/each}"), r#"1:3: Unexpected tag "/each""#);
+    assert_eq!(
+        parse_error("{#each x}a{/if}"),
+        r#"1:11: Unexpected tag "/if""#
+    );
+    assert_eq!(
+        parse_error("{#if x}a{#else}b{/each}"),
+        r#"1:17: Unexpected tag "/each""#,
+    );
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{
+        self,
+        filtered_query::{self, Filter, FilterArg, FilterArgs, FilterArgsError},
+        FilterRegistry, FilteredQueryParser, Parser, SimpleEscapeParser,
+    },
+    GetSpan, IntoTemplateSystem, TemplateSystem,
+};
+use pretty_assertions::assert_eq;
+
+type FilteredTemplateSystem<'a> =
+    TemplateSystem<enclosed::Parser<SimpleEscapeParser, FilteredQueryParser<'a>>, &'a str>;
+
+fn system(registry: &FilterRegistry) -> FilteredTemplateSystem<'_> {
+    Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(FilteredQueryParser::new(registry))
+        .into_template_system()
+}
+
+fn respond(query: &str) -> Result<&'static str, String> {
+    match query {
+        "name" => Ok("  Alice  "),
+        "title" => Ok("Software Engineer"),
+        "empty" => Ok(""),
+        _ => Err(format!("Can't answer {query:?}")),
+    }
+}
+
+#[test]
+fn builtin_filters() {
+    let registry = FilterRegistry::builtin();
+    let actual = system(&registry)
+        .lazy_parse(
+            r#"[{name}] [{name|trim|upper}] [{ title | lower | truncate(8) }] [{empty | default("n/a")}] [{name | default("|}")}]"#,
+        )
+        .to_string(respond)
+        .unwrap();
+    assert_eq!(actual, "[  Alice  ] [ALICE] [software] [n/a] [  Alice  ]",);
+}
+
+#[test]
+fn custom_filter() {
+    struct Repeat;
+
+    impl Filter for Repeat {
+        fn check(&self, args: FilterArgs<'_>) -> Result<(), FilterArgsError> {
+            match (args.count(), args.get(0), args.get(1)) {
+                (2, Some(FilterArg::Integer(_)), Some(FilterArg::String(_))) => Ok(()),
+                (2, _, _) => Err(FilterArgsError::Type {
+                    index: 0,
+                    expected: "an integer".into(),
+                }),
+                (received, _, _) => Err(FilterArgsError::Count {
+                    expected: 2,
+                    received,
+                }),
+            }
+        }
+
+        fn apply(&self, input: String, args: FilterArgs<'_>) -> String {
+            let (Some(FilterArg::Integer(count)), Some(FilterArg::String(separator))) =
+                (args.get(0), args.get(1))
+            else {
+                return input;
+            };
+            vec![input; count as usize].join(separator)
+        }
+    }
+
+    let registry = FilterRegistry::builtin().with_filter("repeat", Repeat);
+    let actual = system(&registry)
+        .eager_parse::<Vec<_>>(r#"{name | trim | repeat(3, ", ")}"#)
+        .unwrap()
+        .to_template()
+        .to_string(respond)
+        .unwrap();
+    assert_eq!(actual, "Alice, Alice, Alice");
+}
+
+#[test]
+fn reject_unknown_filter() {
+    let registry = FilterRegistry::builtin();
+    let system = system(&registry);
+    let error = system
+        .eager_parse::<Vec<_>>("Hello, {name | trim | shout}!")
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error.to_string(),
+        r#"Failed to parse query: Unknown filter "shout""#,
+    );
+    assert!(matches!(
+        &error,
+        enclosed::ParseError::ParseQuery(filtered_query::ParseError::UnknownFilter(name, _))
+            if name == "shout",
+    ));
+    assert_eq!(error.span().start.offset, 22);
+    assert_eq!(error.span().end.offset, 27);
+}
+
+#[test]
+fn reject_invalid_filter_calls() {
+    let registry = FilterRegistry::builtin();
+    let system = system(&registry);
+    let error = |template| {
+        system
+            .eager_parse::<Vec<_>>(template)
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(
+        error("{name | truncate}"),
+        "Failed to parse query: Invalid arguments of filter \"truncate\": Expected 1 argument(s), but received 0",
+    );
+    assert_eq!(
+        error("{name | truncate(\"10\")}"),
+        "Failed to parse query: Invalid arguments of filter \"truncate\": Argument at 0 should be a non-negative integer",
+    );
+    assert_eq!(
+        error("{name | truncate(10}"),
+        "Failed to parse query: Invalid filter call \"truncate(10\"",
+    );
+    assert_eq!(
+        error("{name |}"),
+        "Failed to parse query: Invalid filter call \"\"",
+    );
+}
@@ -0,0 +1,155 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{
+        self,
+        formatted_query::{self, Align, FormatKind, Sign},
+        FormatSpec, FormattedQueryParser, Parser, SimpleEscapeParser,
+    },
+    IntoTemplateSystem, TemplateApplicationError, TemplateSystem,
+};
+use pretty_assertions::assert_eq;
+use std::fmt;
+
+type FormattedTemplateSystem<'a> =
+    TemplateSystem<enclosed::Parser<SimpleEscapeParser, FormattedQueryParser>, &'a str>;
+
+fn system<'a>() -> FormattedTemplateSystem<'a> {
+    Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(FormattedQueryParser)
+        .into_template_system()
+}
+
+#[derive(Debug)]
+enum Response {
+    Text(&'static str),
+    Float(f64),
+    Integer(i64),
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Response::Text(text) => text.fmt(f),
+            Response::Float(float) => float.fmt(f),
+            Response::Integer(integer) => integer.fmt(f),
+        }
+    }
+}
+
+fn respond(query: &str) -> Result<Response, String> {
+    match query {
+        "name" => Ok(Response::Text("Alice")),
+        "env:HOME" => Ok(Response::Text("/home/alice")),
+        "price" => Ok(Response::Float(1234.5678)),
+        "debt" => Ok(Response::Float(-12.5)),
+        "count" => Ok(Response::Integer(42)),
+        _ => Err(format!("Can't answer {query:?}")),
+    }
+}
+
+#[test]
+fn parse_format_spec() {
+    assert_eq!(FormatSpec::parse(""), Some(FormatSpec::default()));
+    assert_eq!(
+        FormatSpec::parse("*^+#010.3e"),
+        Some(FormatSpec {
+            fill: '*',
+            align: Some(Align::Center),
+            sign: Some(Sign::Plus),
+            alternate: true,
+            zero: true,
+            width: Some(10),
+            precision: Some(3),
+            kind: FormatKind::LowerExp,
+        }),
+    );
+    assert_eq!(
+        FormatSpec::parse(">8"),
+        Some(FormatSpec {
+            align: Some(Align::Right),
+            width: Some(8),
+            ..FormatSpec::default()
+        }),
+    );
+    assert_eq!(FormatSpec::parse("<5<"), None);
+    assert_eq!(FormatSpec::parse("10.x"), None);
+    assert_eq!(FormatSpec::parse("y"), None);
+}
+
+#[test]
+fn apply_format_spec() {
+    let actual = system()
+        .lazy_parse(concat!(
+            "[{name}] [{name:8}] [{name:>8}] [{name:-^9}] [{name:.3}] [{env:HOME:}]\n",
+            "[{price:.2}] [{price:>12.1}] [{price:+}] [{debt:+08.2}] [{price:e}]\n",
+            "[{count:x}] [{count:#X}] [{count:#010b}] [{count:o}] [{count:5}] [{count:>5}] [{count:5x}]",
+        ))
+        .to_string(respond)
+        .unwrap();
+    let expected = concat!(
+        "[Alice] [Alice   ] [   Alice] [--Alice--] [Ali] [/home/alice]\n",
+        "[1234.57] [      1234.6] [+1234.5678] [-0012.50] [1.2345678e3]\n",
+        "[2a] [0x2A] [0b00101010] [52] [   42] [   42] [   2a]",
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn pad_like_core_fmt() {
+    let actual = system()
+        .lazy_parse("[{name:8}] [{name:8.3}] [{count:8}] [{count:+8}] [{price:12.1}] [{debt:8}]")
+        .to_string(respond)
+        .unwrap();
+    let expected = format!(
+        "[{:8}] [{:8.3}] [{:8}] [{:+8}] [{:12.1}] [{:8}]",
+        "Alice", "Alice", 42, 42, 1234.5678, -12.5,
+    );
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn fail_to_format_non_numeric_response() {
+    let error = system()
+        .lazy_parse("{name:x}")
+        .to_string(respond)
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        &error,
+        TemplateApplicationError::Query(formatted_query::RenderError::NotANumber(query)) if query == "name",
+    ));
+    assert_eq!(
+        error.to_string(),
+        r#"Response to "name" is not a number of the requested type"#,
+    );
+
+    let mut output = Vec::new();
+    let error = system()
+        .lazy_parse("[{count:x}] [{debt:x}]")
+        .to_writer(&mut output, respond)
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        &error,
+        TemplateApplicationError::Query(formatted_query::RenderError::NotANumber(query)) if query == "debt",
+    ));
+    assert_eq!(String::from_utf8(output).unwrap(), "[2a] [");
+}
+
+#[test]
+fn reject_invalid_format_spec() {
+    let system = system();
+    let error = system
+        .eager_parse::<Vec<_>>("Total: {price:>10.z}")
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error.to_string(),
+        "Failed to parse query: Invalid format specification",
+    );
+    assert!(matches!(
+        error,
+        enclosed::ParseError::ParseQuery(formatted_query::ParseError::InvalidFormatSpec(_)),
+    ));
+}
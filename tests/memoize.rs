@@ -0,0 +1,78 @@
+#![cfg(feature = "std")]
+use lazy_template::{simple_curly_braces, Memoize, TemplateApplicationError};
+use pretty_assertions::assert_eq;
+use std::cell::RefCell;
+
+#[test]
+fn cache_within_a_render() {
+    let calls = RefCell::new(Vec::new());
+    let respond = |query: &str| {
+        calls.borrow_mut().push(query.to_string());
+        Ok::<_, String>(query.to_uppercase())
+    };
+    let system = simple_curly_braces();
+    let output = system
+        .lazy_parse("{user} {item} {user} {user} {item}")
+        .to_string(Memoize::new(respond).into_respond())
+        .unwrap();
+    assert_eq!(output, "USER ITEM USER USER ITEM");
+    assert_eq!(calls.borrow().as_slice(), ["user", "item"]);
+
+    let output = system
+        .lazy_parse("{user}")
+        .to_string(Memoize::new(respond).into_respond())
+        .unwrap();
+    assert_eq!(output, "USER");
+    assert_eq!(calls.borrow().as_slice(), ["user", "item", "user"]);
+}
+
+#[test]
+fn cache_across_renders() {
+    let calls = RefCell::new(0);
+    let mut memoize = Memoize::new(|query: &str| {
+        *calls.borrow_mut() += 1;
+        Ok::<_, String>(query.len())
+    });
+    let system = simple_curly_braces();
+    let parsed_template = system.eager_parse::<Vec<_>>("{abc}-{de}-{abc}").unwrap();
+    for _ in 0..3 {
+        let output = parsed_template
+            .to_template()
+            .to_string(memoize.as_respond())
+            .unwrap();
+        assert_eq!(output, "3-2-3");
+    }
+    assert_eq!(*calls.borrow(), 2);
+    assert_eq!(memoize.cache().len(), 2);
+    assert_eq!(memoize.cache().get("abc"), Some(&3));
+
+    memoize.clear();
+    assert!(memoize.cache().is_empty());
+    parsed_template
+        .to_template()
+        .to_string(memoize.as_respond())
+        .unwrap();
+    assert_eq!(*calls.borrow(), 4);
+}
+
+#[test]
+fn errors_are_not_cached() {
+    let calls = RefCell::new(0);
+    let mut memoize = Memoize::new(|query: &str| {
+        *calls.borrow_mut() += 1;
+        Err::<String, _>(format!("Can't answer {query:?}"))
+    });
+    let system = simple_curly_braces();
+    for _ in 0..2 {
+        let error = system
+            .lazy_parse("{user}")
+            .to_string(memoize.as_respond())
+            .unwrap_err();
+        let TemplateApplicationError::Query(error) = error else {
+            panic!("expecting a query error");
+        };
+        assert_eq!(error, "Can't answer \"user\"");
+    }
+    assert_eq!(*calls.borrow(), 2);
+    assert!(memoize.cache().is_empty());
+}
@@ -0,0 +1,141 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{
+        self,
+        nested_query::{AssembleError, NestedQueryPart},
+        simple_query, NestedQueryParser, OwnedNestedQuery, OwnedSimpleQuery, Parser,
+        SimpleEscapeParser,
+    },
+    iter::ParsedTemplate,
+    IntoTemplateSystem, TemplateApplicationError, TemplateSystem,
+};
+use pretty_assertions::assert_eq;
+use std::{borrow::Cow, fmt, sync::Arc, thread};
+
+type NestedTemplateSystem<'a> =
+    TemplateSystem<enclosed::Parser<SimpleEscapeParser, NestedQueryParser>, Cow<'a, str>>;
+
+fn system<'a>() -> NestedTemplateSystem<'a> {
+    Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(NestedQueryParser)
+        .into_template_system()
+}
+
+#[test]
+fn resolve_inner_queries_first() {
+    let mut queries = Vec::new();
+    let actual = system()
+        .lazy_parse("{greeting}, {name:{lang:{country}}}!")
+        .to_string(|query| {
+            queries.push(query.to_string());
+            match query.as_ref() {
+                "greeting" => Ok("Hello"),
+                "country" => Ok("vn"),
+                "lang:vn" => Ok("vi"),
+                "name:vi" => Ok("Khải"),
+                _ => Err(format!("Can't answer {query:?}")),
+            }
+        })
+        .unwrap();
+    dbg!(&actual);
+    assert_eq!(actual, "Hello, Khải!");
+    assert_eq!(queries, ["greeting", "country", "lang:vn", "name:vi"]);
+}
+
+#[test]
+fn borrow_queries_without_inner_queries() {
+    let actual = system()
+        .lazy_parse("{foo} {}")
+        .to_string(|query| match query {
+            Cow::Borrowed(query) => Ok(format!("[{query}]")),
+            Cow::Owned(query) => Err(format!("{query:?} should be borrowed")),
+        })
+        .unwrap();
+    assert_eq!(actual, "[foo] []");
+}
+
+#[test]
+fn parts() {
+    let system = system();
+    let parsed_template = system.eager_parse::<Vec<_>>("{a{b}c{d{e}}}").unwrap();
+    let Some(enclosed::Segment::Expression(query, _)) = parsed_template.segments().next() else {
+        panic!("expecting an expression");
+    };
+    let actual: Vec<_> = query
+        .parts()
+        .map(|part| match part {
+            NestedQueryPart::Text(text) => format!("Text({text:?})"),
+            NestedQueryPart::Query(query) => format!("Query({:?})", query.text()),
+        })
+        .collect();
+    let expected = [
+        r#"Text("a")"#,
+        r#"Query("b")"#,
+        r#"Text("c")"#,
+        r#"Query("d{e}")"#,
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn reject_unbalanced_brackets() {
+    let error = system().eager_parse::<Vec<_>>("{foo{bar}").unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        enclosed::ParseError::ParseQuery(simple_query::ParseError::UnexpectedEndOfInput(_)),
+    ));
+}
+
+/// Response whose [`Display`](fmt::Display) implementation always fails.
+struct Unprintable;
+
+impl fmt::Display for Unprintable {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Err(fmt::Error)
+    }
+}
+
+#[test]
+fn inner_write_error() {
+    let mut queries = Vec::new();
+    let error = system()
+        .lazy_parse("{name:{lang}}")
+        .to_string(|query| {
+            queries.push(query.to_string());
+            Ok::<_, String>(Unprintable)
+        })
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(AssembleError::Write(fmt::Error)),
+    ));
+    assert_eq!(queries, ["lang"]);
+}
+
+#[test]
+fn into_owned() {
+    let template_string = String::from("{greeting}, {name:{lang}}!");
+    let parsed_template: ParsedTemplate<Vec<enclosed::Segment<OwnedNestedQuery, Arc<str>>>, _> =
+        system()
+            .eager_parse::<Vec<_>>(&template_string)
+            .unwrap()
+            .into_owned();
+    drop(template_string);
+    let output = thread::spawn(move || {
+        parsed_template
+            .to_template()
+            .to_string(|query: OwnedSimpleQuery| match query.as_ref() {
+                "greeting" => Ok("Hello"),
+                "lang" => Ok("vi"),
+                "name:vi" => Ok("Khải"),
+                _ => Err(format!("Can't answer {query:?}")),
+            })
+    })
+    .join()
+    .unwrap()
+    .unwrap();
+    assert_eq!(output, "Hello, Khải!");
+}
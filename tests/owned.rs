@@ -0,0 +1,173 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{
+        self, filtered_query, simple_query, DefaultQueryParser, FilterRegistry,
+        FilteredQueryParser, FormattedQueryParser, OwnedSimpleQuery, Parser, Segment,
+        SimpleEscapeParser,
+    },
+    iter::ParsedTemplate,
+    simple_curly_braces, GetSpan, IntoOwned, IntoTemplateSystem, Position, Span,
+};
+use pretty_assertions::assert_eq;
+use std::{collections::HashMap, sync::Arc, thread};
+
+type OwnedParsedTemplate =
+    ParsedTemplate<Vec<Segment<OwnedSimpleQuery, Arc<str>>>, OwnedSimpleQuery>;
+
+fn respond(query: OwnedSimpleQuery) -> Result<&'static str, String> {
+    match query.as_ref() {
+        "name" => Ok("Alice"),
+        "age" => Ok("20"),
+        _ => Err(format!("Can't answer {query:?}")),
+    }
+}
+
+fn assert_static_send_sync<Value: Send + Sync + 'static>(value: Value) -> Value {
+    value
+}
+
+#[test]
+fn into_owned() {
+    let system = simple_curly_braces();
+    let template_string = String::from("{name} is {age} \\{years\\} old");
+    let parsed_template: OwnedParsedTemplate = system
+        .eager_parse::<Vec<_>>(&template_string)
+        .unwrap()
+        .into_owned();
+    drop(template_string);
+    let parsed_template = assert_static_send_sync(parsed_template);
+
+    let output = thread::spawn(move || parsed_template.to_template().to_string(respond))
+        .join()
+        .unwrap()
+        .unwrap();
+    assert_eq!(output, "Alice is 20 {years} old");
+}
+
+#[test]
+fn eager_parse_owned() {
+    let system = simple_curly_braces();
+    let mut cache: HashMap<&str, OwnedParsedTemplate> = HashMap::new();
+    for (key, template_string) in [("greeting", "Hello, {name}!"), ("age", "{age}")] {
+        let parsed_template = system
+            .eager_parse_owned(template_string.to_string())
+            .unwrap();
+        cache.insert(key, parsed_template);
+    }
+
+    let output = cache["greeting"].to_template().to_string(respond).unwrap();
+    assert_eq!(output, "Hello, Alice!");
+    let output = cache["age"].to_template().to_string(respond).unwrap();
+    assert_eq!(output, "20");
+
+    let queries: Vec<_> = cache["greeting"].queries().collect();
+    assert_eq!(queries, [OwnedSimpleQuery::from("name")]);
+    assert_eq!(
+        cache["greeting"].segments().nth(1).unwrap().span(),
+        Span {
+            start: Position {
+                offset: 7,
+                line: 1,
+                column: 8,
+            },
+            end: Position {
+                offset: 13,
+                line: 1,
+                column: 14,
+            },
+        },
+    );
+}
+
+#[test]
+fn eager_parse_owned_error() {
+    let system = simple_curly_braces();
+    let error = system
+        .eager_parse_owned::<Vec<_>, _, _>("Hello, {name".to_string())
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        enclosed::ParseError::ParseQuery(simple_query::ParseError::UnexpectedEndOfInput(_)),
+    ));
+}
+
+#[test]
+fn default_query_into_owned() {
+    let system = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(DefaultQueryParser)
+        .into_template_system::<&str>();
+    let parsed_template = system
+        .eager_parse_owned::<Vec<_>, _, _>(
+            r#"{name ?? "anonymous"} from {city:-nowhere}"#.to_string(),
+        )
+        .unwrap();
+    let parsed_template = assert_static_send_sync(parsed_template);
+    let output = thread::spawn(move || parsed_template.to_template().to_string(respond))
+        .join()
+        .unwrap()
+        .unwrap();
+    assert_eq!(output, "Alice from nowhere");
+}
+
+#[test]
+fn formatted_query_into_owned() {
+    let system = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(FormattedQueryParser)
+        .into_template_system::<&str>();
+    let parsed_template = system
+        .eager_parse_owned::<Vec<_>, _, _>("[{name:>7}] [{age:.1}]".to_string())
+        .unwrap();
+    let parsed_template = assert_static_send_sync(parsed_template);
+    let output = thread::spawn(move || parsed_template.to_template().to_string(respond))
+        .join()
+        .unwrap()
+        .unwrap();
+    assert_eq!(output, "[  Alice] [2]");
+}
+
+#[test]
+fn filtered_query_into_owned() {
+    let registry = FilterRegistry::builtin();
+    let system = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(FilteredQueryParser::new(&registry))
+        .into_template_system::<&str>();
+    let template_string = String::from("{name | upper | truncate(3)} is {age}");
+    let parsed_template: ParsedTemplate<Vec<Segment<_, Arc<str>>>, OwnedSimpleQuery> = system
+        .eager_parse::<Vec<_>>(&template_string)
+        .unwrap()
+        .into_owned();
+    drop(template_string);
+    drop(system);
+    drop(registry);
+    let parsed_template = assert_static_send_sync(parsed_template);
+    let output = thread::spawn(move || parsed_template.to_template().to_string(respond))
+        .join()
+        .unwrap()
+        .unwrap();
+    assert_eq!(output, "ALI is 20");
+}
+
+#[test]
+fn filtered_query_into_owned_error() {
+    let registry = FilterRegistry::builtin();
+    let system = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(FilteredQueryParser::new(&registry))
+        .into_template_system::<&str>();
+    let template_string = String::from("{name | shout}");
+    let error: enclosed::ParseError<_, filtered_query::ParseError<'static>> = system
+        .eager_parse::<Vec<_>>(&template_string)
+        .unwrap_err()
+        .into_owned();
+    drop(template_string);
+    dbg!(&error);
+    assert!(matches!(
+        &error,
+        enclosed::ParseError::ParseQuery(filtered_query::ParseError::UnknownFilter(name, _))
+            if name == "shout",
+    ));
+}
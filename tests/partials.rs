@@ -0,0 +1,209 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{
+        self,
+        partial::{self, PartialSegment},
+        Parser, PartialParser, SimpleEscapeParser, SimpleQuery, SimpleQueryParser,
+    },
+    simple_curly_braces, GetSpan, IntoTemplateSystem, PartialError, TemplateSystem,
+};
+use pretty_assertions::assert_eq;
+use std::{borrow::Cow, cell::RefCell};
+
+fn respond(query: &str) -> Result<&'static str, String> {
+    match query {
+        "name" => Ok("Alice"),
+        "company" => Ok("ACME"),
+        _ => Err(format!("Can't answer {query:?}")),
+    }
+}
+
+fn load(name: &str) -> Result<Cow<'static, str>, String> {
+    let template = match name {
+        "header" => "Hello, {name}!\n",
+        "footer" => "--\n{>signature}",
+        "signature" => "{company}",
+        "self" => "[{>self}]",
+        "ping" => "ping {>pong}",
+        "pong" => "pong {>ping}",
+        "broken" => "{name",
+        _ => return Err(format!("No partial named {name:?}")),
+    };
+    Ok(Cow::Borrowed(template))
+}
+
+type PartialTemplateSystem = TemplateSystem<
+    PartialParser<enclosed::Parser<SimpleEscapeParser, SimpleQueryParser>>,
+    SimpleQuery<'static>,
+>;
+
+fn system() -> PartialTemplateSystem {
+    let parser = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(SimpleQueryParser);
+    PartialParser::new(parser).into_template_system()
+}
+
+#[test]
+fn parse_partial_segment() {
+    let system = system();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>("a{> header }b{>footer}")
+        .unwrap();
+    let partials: Vec<_> = parsed_template
+        .segments()
+        .filter_map(|segment| match *segment {
+            PartialSegment::Partial(name, span) => Some((name, span.to_string())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        partials,
+        [
+            ("header", "1:2".to_string()),
+            ("footer", "1:14".to_string())
+        ]
+    );
+}
+
+#[test]
+fn include_nested_partials() {
+    let output = system()
+        .partials(load)
+        .to_string("{>header}Your order has shipped.\n{>footer}", respond)
+        .unwrap();
+    assert_eq!(output, "Hello, Alice!\nYour order has shipped.\n--\nACME");
+}
+
+#[test]
+fn include_same_partial_twice() {
+    let output = system()
+        .partials(load)
+        .to_string("{>signature} & {>signature}", respond)
+        .unwrap();
+    assert_eq!(output, "ACME & ACME");
+}
+
+#[test]
+fn load_owned_templates() {
+    let loaded = RefCell::new(Vec::new());
+    let output = system()
+        .partials(|name: &str| {
+            loaded.borrow_mut().push(name.to_string());
+            Ok::<_, String>(format!("<{name}:{{name}}>"))
+        })
+        .to_string("{>a}{>b}", respond)
+        .unwrap();
+    assert_eq!(output, "<a:Alice><b:Alice>");
+    assert_eq!(loaded.borrow().as_slice(), ["a", "b"]);
+}
+
+#[test]
+fn partials_are_opt_in() {
+    let output = simple_curly_braces()
+        .lazy_parse("[{>header}]")
+        .to_string(|query| match query {
+            ">header" => Ok("query"),
+            _ => Err(format!("Can't answer {query:?}")),
+        })
+        .unwrap();
+    assert_eq!(output, "[query]");
+}
+
+#[test]
+fn detect_cycles() {
+    let system = system();
+    let partials = system.partials(load);
+    let error = partials.to_string("{>self}", respond).unwrap_err();
+    dbg!(&error);
+    assert!(matches!(&error, PartialError::Cycle(name) if name == "self"));
+    assert_eq!(
+        error.to_string(),
+        r#"Partial "self" is included recursively"#
+    );
+    let error = partials.to_string("{>ping}", respond).unwrap_err();
+    dbg!(&error);
+    assert!(matches!(&error, PartialError::Cycle(name) if name == "ping"));
+}
+
+#[test]
+fn limit_depth() {
+    let system = system();
+    let partials = system.partials(load);
+    let output = partials
+        .with_max_depth(2)
+        .to_string("{>footer}", respond)
+        .unwrap();
+    assert_eq!(output, "--\nACME");
+    let error = partials
+        .with_max_depth(1)
+        .to_string("{>footer}", respond)
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(error, PartialError::DepthLimit(1)));
+    assert_eq!(
+        error.to_string(),
+        "Partials are nested deeper than 1 levels"
+    );
+}
+
+#[test]
+fn load_error() {
+    let error = system()
+        .partials(load)
+        .to_string("{>missing}", respond)
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error.to_string(),
+        r#"Failed to load partial "missing": No partial named "missing""#,
+    );
+}
+
+#[test]
+fn parse_error_in_partial() {
+    let error = system()
+        .partials(load)
+        .to_string("{>broken}", respond)
+        .unwrap_err();
+    dbg!(&error);
+    let PartialError::ParsePartial { name, error } = error else {
+        panic!("Expected a parse error in a partial");
+    };
+    assert_eq!(name, "broken");
+    assert_eq!(
+        format!("{}: {error}", error.span()),
+        "1:6: Failed to parse query: Unexpected end of input",
+    );
+}
+
+#[test]
+fn parse_error_in_partial_tag() {
+    let system = system();
+    let error = system.eager_parse::<Vec<_>>("a {> } b").unwrap_err();
+    dbg!(&error);
+    assert!(matches!(error, partial::ParseError::MissingName(_)));
+    assert_eq!(
+        format!("{}: {error}", error.span()),
+        "1:3: Partial has no name"
+    );
+    let error = system.eager_parse::<Vec<_>>("a {>header").unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        format!("{}: {error}", error.span()),
+        "1:11: Unexpected end of input"
+    );
+}
+
+#[test]
+fn query_error_in_partial() {
+    let error = system()
+        .partials(|_: &str| Ok::<_, String>(Cow::Borrowed("{unknown}")))
+        .to_string("{>partial}", respond)
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        PartialError::Query(message) if message == r#"Can't answer "unknown""#,
+    ));
+}
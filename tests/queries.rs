@@ -0,0 +1,120 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{
+        self, ComponentParserInput, FilterRegistry, FilteredQueryParser, Parser,
+        SimpleEscapeParser, SimpleQueryParser,
+    },
+    simple_curly_braces, IntoTemplateSystem, Parse,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn lazy_template_queries() {
+    let system = simple_curly_braces();
+    let queries: Vec<_> = system
+        .lazy_parse("\\{not a query\\} {a} {b}\n{c}{a}")
+        .queries()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    dbg!(&queries);
+    assert_eq!(queries, ["a", "b", "c", "a"]);
+}
+
+#[test]
+fn lazy_template_queries_stop_at_error() {
+    let system = simple_curly_braces();
+    let queries: Vec<_> = system.lazy_parse("{a} {b").queries().collect();
+    dbg!(&queries);
+    assert_eq!(queries.len(), 2);
+    assert!(matches!(queries[0], Ok("a")));
+    assert!(matches!(
+        queries[1],
+        Err(enclosed::ParseError::ParseQuery(
+            enclosed::simple_query::ParseError::UnexpectedEndOfInput(_)
+        )),
+    ));
+}
+
+#[test]
+fn lazy_template_unique_queries() {
+    let system = simple_curly_braces();
+    let queries = system
+        .lazy_parse("{b} {a} {b} {c} {a}")
+        .unique_queries()
+        .unwrap();
+    dbg!(&queries);
+    assert_eq!(queries, ["b", "a", "c"]);
+
+    let error = system
+        .lazy_parse("{b} {a} } {c}")
+        .unique_queries()
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        enclosed::ParseError::ParseQuery(enclosed::simple_query::ParseError::UnexpectedToken(
+            token,
+            _
+        )) if token == "}",
+    ));
+}
+
+#[test]
+fn parsed_template_queries() {
+    let system = simple_curly_braces();
+    let parsed_template = system.eager_parse::<Vec<_>>("{b} {a} {b} {c} {a}").unwrap();
+    let queries: Vec<_> = parsed_template.queries().collect();
+    dbg!(&queries);
+    assert_eq!(queries, ["b", "a", "b", "c", "a"]);
+    assert_eq!(parsed_template.unique_queries(), ["b", "a", "c"]);
+}
+
+#[test]
+fn structured_queries() {
+    let registry = FilterRegistry::builtin();
+    let system = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(FilteredQueryParser::new(&registry))
+        .into_template_system::<&str>();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>("{name | upper} {title} {name | lower}")
+        .unwrap();
+    let queries: Vec<_> = parsed_template
+        .queries()
+        .map(|query| query.query())
+        .collect();
+    dbg!(&queries);
+    assert_eq!(queries, ["name", "title", "name"]);
+}
+
+/// Query type of a downstream crate, which is sent to the respond function as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Key<'a>(&'a str);
+
+struct KeyParser;
+
+impl<'a> Parse<'a, ComponentParserInput<'a>> for KeyParser {
+    type Output = Key<'a>;
+    type Error = <SimpleQueryParser as Parse<'a, ComponentParserInput<'a>>>::Error;
+    fn parse(&'a self, input: ComponentParserInput<'a>) -> Result<(Key<'a>, &'a str), Self::Error> {
+        let (query, rest) = SimpleQueryParser.parse(input)?;
+        Ok((Key(query.trim()), rest))
+    }
+}
+
+#[test]
+fn custom_queries() {
+    let output = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(KeyParser)
+        .into_template_system::<Key>()
+        .lazy_parse("{ name } is { age }")
+        .to_string(|Key(key)| match key {
+            "name" => Ok("Alice"),
+            "age" => Ok("20"),
+            _ => Err(format!("Can't answer {key:?}")),
+        })
+        .unwrap();
+    dbg!(&output);
+    assert_eq!(output, "Alice is 20");
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{self, FilterRegistry, FilteredQueryParser, Parser, SimpleEscapeParser},
+    simple_curly_braces, GetSpan, IntoTemplateSystem,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn without_errors() {
+    let system = simple_curly_braces();
+    let (parsed_template, errors) =
+        system.recovering_parse::<Vec<_>, Vec<_>>("{name} is {age} years old");
+    dbg!(&parsed_template, &errors);
+    assert!(errors.is_empty());
+    let output = parsed_template
+        .to_template()
+        .to_string(|query| match query {
+            "name" => Ok("Alice"),
+            "age" => Ok("20"),
+            _ => Err(format!("Can't answer {query:?}")),
+        })
+        .unwrap();
+    assert_eq!(output, "Alice is 20 years old");
+}
+
+#[test]
+fn collect_all_errors() {
+    let system = simple_curly_braces();
+    let (parsed_template, errors) =
+        system.recovering_parse::<Vec<_>, Vec<_>>("a } b\n\\q {c}\n}} d {e");
+    dbg!(&parsed_template, &errors);
+
+    let output = parsed_template
+        .to_template()
+        .to_string(|query| Ok::<_, String>(query.to_uppercase()))
+        .unwrap();
+    assert_eq!(output, "a  b\n C\n d ");
+
+    let actual: Vec<_> = errors
+        .iter()
+        .map(|error| (error.span().to_string(), error.to_string()))
+        .collect();
+    let expected = [
+        ("1:3", "Failed to parse query: Unexpected token \"}\""),
+        ("2:2", "Failed to escape: Unsupported escape code 'q'"),
+        ("3:1", "Failed to parse query: Unexpected token \"}\""),
+        ("3:2", "Failed to parse query: Unexpected token \"}\""),
+        ("3:8", "Failed to parse query: Unexpected end of input"),
+    ];
+    let expected: Vec<_> = expected
+        .map(|(span, message)| (span.to_string(), message.to_string()))
+        .into();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn skip_malformed_query() {
+    let registry = FilterRegistry::builtin();
+    let system = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(FilteredQueryParser::new(&registry))
+        .into_template_system::<&str>();
+    let (parsed_template, errors) = system.recovering_parse::<Vec<_>, Vec<_>>(
+        "[{name | bogus}] [{name | truncate(\"x\")}] [{name | upper}]",
+    );
+    dbg!(&parsed_template, &errors);
+
+    let output = parsed_template
+        .to_template()
+        .to_string(|query| match query {
+            "name" => Ok("Alice"),
+            _ => Err(format!("Can't answer {query:?}")),
+        })
+        .unwrap();
+    assert_eq!(output, "[] [] [ALICE]");
+
+    let actual: Vec<_> = errors
+        .iter()
+        .map(|error| (error.span().to_string(), error.to_string()))
+        .collect();
+    let expected = [
+        ("1:10", "Failed to parse query: Unknown filter \"bogus\""),
+        (
+            "1:27",
+            "Failed to parse query: Invalid arguments of filter \"truncate\": \
+             Argument at 0 should be a non-negative integer",
+        ),
+    ];
+    let expected: Vec<_> = expected
+        .map(|(span, message)| (span.to_string(), message.to_string()))
+        .into();
+    assert_eq!(actual, expected);
+    assert!(matches!(
+        &errors[0],
+        enclosed::ParseError::ParseQuery(enclosed::filtered_query::ParseError::UnknownFilter(
+            name,
+            _
+        )) if name == "bogus",
+    ));
+}
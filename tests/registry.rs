@@ -0,0 +1,143 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{self, simple_query, OwnedSimpleQuery},
+    simple_curly_braces, GetSpan, RegistryRenderError, TemplateRegistry,
+};
+use pretty_assertions::assert_eq;
+use std::{cell::RefCell, sync::Arc, thread};
+
+fn respond(query: OwnedSimpleQuery) -> Result<&'static str, String> {
+    match query.as_ref() {
+        "name" => Ok("Alice"),
+        "age" => Ok("20"),
+        _ => Err(format!("Can't answer {query:?}")),
+    }
+}
+
+#[test]
+fn render_by_name() {
+    let mut registry = TemplateRegistry::new(simple_curly_braces());
+    assert!(registry.is_empty());
+    let template_string = String::from("{name} is {age} years old");
+    let previous = registry.insert("bio", &template_string).unwrap();
+    assert!(previous.is_none());
+    drop(template_string);
+    registry.insert("greeting", "Hello, {name}!").unwrap();
+    assert_eq!(registry.len(), 2);
+    let mut names: Vec<_> = registry.names().collect();
+    names.sort();
+    assert_eq!(names, ["bio", "greeting"]);
+    assert_eq!(
+        registry.render("bio", respond).unwrap(),
+        "Alice is 20 years old"
+    );
+    assert_eq!(
+        registry.render("greeting", respond).unwrap(),
+        "Hello, Alice!"
+    );
+}
+
+#[test]
+fn report_all_parse_errors() {
+    let mut registry = TemplateRegistry::new(simple_curly_braces());
+    let errors = registry.insert("broken", "{name}} \\z {age").unwrap_err();
+    dbg!(&errors);
+    let errors: Vec<_> = errors
+        .iter()
+        .map(|error| format!("{}: {error}", error.span()))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            "1:7: Failed to parse query: Unexpected token \"}\"",
+            "1:10: Failed to escape: Unsupported escape code 'z'",
+            "1:16: Failed to parse query: Unexpected end of input",
+        ],
+    );
+    assert!(!registry.contains("broken"));
+}
+
+#[test]
+fn hot_replacement() {
+    let mut registry = TemplateRegistry::new(simple_curly_braces());
+    registry.insert("greeting", "Hello, {name}!").unwrap();
+    registry.insert("bio", "{name} is {age}").unwrap();
+    let bio_segments = registry.get("bio").unwrap().segments().as_slice().as_ptr();
+
+    let previous = registry.insert("greeting", "Hi, {name}!").unwrap().unwrap();
+    assert_eq!(
+        previous.to_template().to_string(respond).unwrap(),
+        "Hello, Alice!"
+    );
+    assert_eq!(registry.render("greeting", respond).unwrap(), "Hi, Alice!");
+    assert_eq!(
+        registry.get("bio").unwrap().segments().as_slice().as_ptr(),
+        bio_segments,
+        "other templates should not be re-parsed",
+    );
+
+    let errors = registry.insert("greeting", "Hey, {name").unwrap_err();
+    dbg!(&errors);
+    assert!(matches!(
+        errors.as_slice(),
+        [enclosed::ParseError::ParseQuery(
+            simple_query::ParseError::UnexpectedEndOfInput(_)
+        )],
+    ));
+    assert_eq!(registry.render("greeting", respond).unwrap(), "Hi, Alice!");
+
+    registry.remove("greeting").unwrap();
+    assert!(!registry.contains("greeting"));
+}
+
+#[test]
+fn unknown_template() {
+    let registry = TemplateRegistry::new(simple_curly_braces());
+    let error = registry.render("missing", respond).unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error,
+        RegistryRenderError::UnknownTemplate("missing".to_string())
+    );
+    assert_eq!(error.to_string(), r#"No template is named "missing""#);
+}
+
+#[test]
+fn query_error() {
+    let queries = RefCell::new(Vec::new());
+    let mut registry = TemplateRegistry::new(simple_curly_braces());
+    registry.insert("bio", "{name} is {height} tall").unwrap();
+    let mut output = String::new();
+    let error = registry
+        .write_to(&mut output, "bio", |query: OwnedSimpleQuery| {
+            queries.borrow_mut().push(query.clone());
+            respond(query)
+        })
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error,
+        RegistryRenderError::Query(r#"Can't answer "height""#.to_string()),
+    );
+    assert_eq!(output, "Alice is ");
+    assert_eq!(
+        queries.borrow().as_slice(),
+        [Arc::from("name"), Arc::from("height")],
+    );
+}
+
+#[test]
+fn share_across_threads() {
+    let mut registry = TemplateRegistry::new(simple_curly_braces());
+    registry.insert("greeting", "Hello, {name}!").unwrap();
+    let registry = Arc::new(registry);
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let registry = Arc::clone(&registry);
+            thread::spawn(move || registry.render("greeting", respond).unwrap())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), "Hello, Alice!");
+    }
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    responders::{self, MissingPositional, PrefixError},
+    simple_curly_braces, TemplateApplicationError, UnknownQuery,
+};
+use pretty_assertions::assert_eq;
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+fn from_hash_map() {
+    let map = HashMap::from([("name".to_string(), "Alice"), ("age".to_string(), "20")]);
+    let output = simple_curly_braces()
+        .lazy_parse("{name} is {age} years old")
+        .to_string(responders::from_map(&map))
+        .unwrap();
+    assert_eq!(output, "Alice is 20 years old");
+}
+
+#[test]
+fn from_btree_map() {
+    let map = BTreeMap::from([("name", 'A'), ("age", 'B')]);
+    let output = simple_curly_braces()
+        .lazy_parse("{name}{age}{name}")
+        .to_string(responders::from_map(&map))
+        .unwrap();
+    assert_eq!(output, "ABA");
+}
+
+#[test]
+fn from_map_unknown_query() {
+    let map = BTreeMap::from([("name", "Alice")]);
+    let error = simple_curly_braces()
+        .lazy_parse("{name} is {age} years old")
+        .to_string(responders::from_map(&map))
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(error.to_string(), r#"Unknown query "age""#);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(UnknownQuery { query }) if query == "age",
+    ));
+}
+
+#[test]
+fn from_env() {
+    std::env::set_var("LAZY_TEMPLATE_TEST_RESPONDERS", "hello");
+    let output = simple_curly_braces()
+        .lazy_parse("{LAZY_TEMPLATE_TEST_RESPONDERS}, world")
+        .to_string(responders::from_env())
+        .unwrap();
+    assert_eq!(output, "hello, world");
+}
+
+#[test]
+fn from_env_missing() {
+    let error = simple_curly_braces()
+        .lazy_parse("{LAZY_TEMPLATE_TEST_RESPONDERS_MISSING}")
+        .to_string(responders::from_env())
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error.to_string(),
+        r#"Cannot read environment variable "LAZY_TEMPLATE_TEST_RESPONDERS_MISSING": environment variable not found"#,
+    );
+}
+
+#[test]
+fn from_file() {
+    let expected = std::fs::read_to_string("rust-toolchain").unwrap();
+    let output = simple_curly_braces()
+        .lazy_parse("[{rust-toolchain}]")
+        .to_string(responders::from_file())
+        .unwrap();
+    assert_eq!(output, format!("[{expected}]"));
+}
+
+#[test]
+fn from_file_missing() {
+    let error = simple_curly_braces()
+        .lazy_parse("{this-file-does-not-exist}")
+        .to_string(responders::from_file())
+        .unwrap_err();
+    dbg!(&error);
+    let TemplateApplicationError::Query(error) = error else {
+        panic!("Expected a query error");
+    };
+    assert_eq!(error.path, "this-file-does-not-exist");
+    assert_eq!(error.error.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn from_fn() {
+    let output = simple_curly_braces()
+        .lazy_parse("{3} + {4}")
+        .to_string(responders::from_fn(|query| {
+            query.parse::<u32>().ok().map(|x| x * 10)
+        }))
+        .unwrap();
+    assert_eq!(output, "30 + 40");
+
+    let error = simple_curly_braces()
+        .lazy_parse("{3} + {x}")
+        .to_string(responders::from_fn(|query| query.parse::<u32>().ok()))
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(error.to_string(), r#"Unknown query "x""#);
+}
+
+#[test]
+fn positional() {
+    let output = simple_curly_braces()
+        .lazy_parse("{} and {} are {}")
+        .to_string(responders::positional(["Alice", "Bob", "friends"]))
+        .unwrap();
+    assert_eq!(output, "Alice and Bob are friends");
+}
+
+#[test]
+fn positional_missing() {
+    let error = simple_curly_braces()
+        .lazy_parse("{} and {} are {}")
+        .to_string(responders::positional(["Alice"]))
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error.to_string(),
+        "Positional argument at 1 is not provided"
+    );
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(MissingPositional { index: 1 }),
+    ));
+}
+
+#[test]
+fn strip_prefix() {
+    let map = BTreeMap::from([("name", "Alice")]);
+    let output = simple_curly_braces()
+        .lazy_parse("Hello, {map:name}!")
+        .to_string(responders::strip_prefix("map:", responders::from_map(&map)))
+        .unwrap();
+    assert_eq!(output, "Hello, Alice!");
+}
+
+#[test]
+fn strip_prefix_mismatch() {
+    let map = BTreeMap::from([("name", "Alice")]);
+    let error = simple_curly_braces()
+        .lazy_parse("Hello, {name}!")
+        .to_string(responders::strip_prefix("map:", responders::from_map(&map)))
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(error.to_string(), r#"Unknown query "name""#);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(PrefixError::Mismatch(UnknownQuery { query })) if query == "name",
+    ));
+}
+
+#[test]
+fn strip_prefix_respond_error() {
+    let map = BTreeMap::from([("name", "Alice")]);
+    let error = simple_curly_braces()
+        .lazy_parse("Hello, {map:age}!")
+        .to_string(responders::strip_prefix("map:", responders::from_map(&map)))
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(error.to_string(), r#"Unknown query "age""#);
+    assert!(matches!(
+        error,
+        TemplateApplicationError::Query(PrefixError::Respond(UnknownQuery { query })) if query == "age",
+    ));
+}
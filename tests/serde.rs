@@ -0,0 +1,195 @@
+#![cfg(feature = "serde")]
+use lazy_template::{
+    enclosed::{
+        self, block, simple_escape, simple_query, BlockParser, DefaultQueryParser,
+        FormattedQueryParser, OwnedSimpleQuery, Parser, ParserConfig, Segment, SimpleEscapeParser,
+        SimpleQueryParser,
+    },
+    iter::ParsedTemplate,
+    simple_curly_braces, GetSpan, IntoTemplateSystem,
+};
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use std::sync::Arc;
+
+#[test]
+fn parsed_template_round_trip() {
+    let system = simple_curly_braces();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>("Hello, {name}! \\{\\}")
+        .unwrap();
+    let json = serde_json::to_string(&parsed_template).unwrap();
+    dbg!(&json);
+
+    // borrowed strings can only be deserialized from JSON strings without escape sequences
+    let deserialized: ParsedTemplate<Vec<Segment<&str, &str>>, &str> =
+        serde_json::from_str(&json).unwrap();
+    let spans = |parsed_template: &ParsedTemplate<Vec<Segment<&str, &str>>, &str>| {
+        parsed_template
+            .segments()
+            .map(GetSpan::span)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(spans(&deserialized), spans(&parsed_template));
+    let output = deserialized
+        .to_template()
+        .to_string(|query| (query == "name").then_some("Alice").ok_or("Invalid query"))
+        .unwrap();
+    assert_eq!(output, "Hello, Alice! {}");
+}
+
+#[test]
+fn owned_parsed_template_round_trip() {
+    let system = simple_curly_braces();
+    let json = system
+        .eager_parse_owned::<Vec<_>, _, _>("Say \"{word}\"".to_string())
+        .unwrap()
+        .pipe_ref(serde_json::to_string)
+        .unwrap();
+    dbg!(&json);
+
+    let deserialized: ParsedTemplate<Vec<Segment<OwnedSimpleQuery, Arc<str>>>, OwnedSimpleQuery> =
+        serde_json::from_str(&json).unwrap();
+    let output = deserialized
+        .to_template()
+        .to_string(|query| Ok::<_, String>(query.to_uppercase()))
+        .unwrap();
+    assert_eq!(output, "Say \"WORD\"");
+}
+
+#[test]
+fn structured_queries_round_trip() {
+    let system = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(DefaultQueryParser)
+        .into_template_system::<&str>();
+    let json = system
+        .eager_parse::<Vec<_>>("{name:-anonymous}")
+        .unwrap()
+        .pipe_ref(serde_json::to_string)
+        .unwrap();
+    let deserialized: ParsedTemplate<Vec<Segment<enclosed::DefaultQuery, &str>>, &str> =
+        serde_json::from_str(&json).unwrap();
+    let output = deserialized
+        .to_template()
+        .to_string(|_| Err::<&str, _>("unknown"))
+        .unwrap();
+    assert_eq!(output, "anonymous");
+
+    let system = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(FormattedQueryParser)
+        .into_template_system::<&str>();
+    let json = system
+        .eager_parse::<Vec<_>>("[{count:>5}]")
+        .unwrap()
+        .pipe_ref(serde_json::to_string)
+        .unwrap();
+    let deserialized: ParsedTemplate<Vec<Segment<enclosed::FormattedQuery, &str>>, &str> =
+        serde_json::from_str(&json).unwrap();
+    let output = deserialized
+        .to_template()
+        .to_string(|_| Ok::<_, String>(42))
+        .unwrap();
+    assert_eq!(output, "[   42]");
+}
+
+#[test]
+fn parser_config_round_trip() {
+    let json = r#"{"open_bracket":"<%","close_bracket":"%>"}"#.to_string();
+    let config: ParserConfig = serde_json::from_str(&json).unwrap();
+    drop(json);
+    assert_eq!(config.open_bracket(), "<%");
+    assert_eq!(config.close_bracket(), "%>");
+    assert_eq!(
+        serde_json::to_string(&config).unwrap(),
+        r#"{"open_bracket":"<%","close_bracket":"%>"}"#,
+    );
+
+    let system = Parser::curly_braces()
+        .with_config(config)
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(enclosed::SimpleQueryParser)
+        .into_template_system::<&str>();
+    let output = system
+        .lazy_parse("Hello, <%name%>!")
+        .to_string(|query| Ok::<_, String>(query.to_uppercase()))
+        .unwrap();
+    assert_eq!(output, "Hello, NAME!");
+}
+
+#[test]
+fn reject_empty_delimiters() {
+    let error = serde_json::from_str::<ParserConfig>(r#"{"open_bracket":"","close_bracket":"}"}"#)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Opening delimiter must not be empty");
+    let json = r#"{"open_bracket":"{","close_bracket":"}","comment":{"open_bracket":"{#","close_bracket":""}}"#;
+    let error = serde_json::from_str::<ParserConfig>(json).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Closing delimiter must not be empty at line 1 column 91",
+    );
+}
+
+#[test]
+fn comment_config_round_trip() {
+    let json = r##"{"open_bracket":"{","close_bracket":"}","comment":{"open_bracket":"{#","close_bracket":"#}"}}"##;
+    let config: ParserConfig = serde_json::from_str(json).unwrap();
+    let comment = config.comment().unwrap();
+    assert_eq!(
+        (comment.open_bracket(), comment.close_bracket()),
+        ("{#", "#}")
+    );
+    assert_eq!(serde_json::to_string(&config).unwrap(), json);
+
+    let system = Parser::curly_braces()
+        .with_config(config)
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(enclosed::SimpleQueryParser)
+        .into_template_system::<&str>();
+    let output = system
+        .lazy_parse("Hello, {name}!{# note #}")
+        .to_string(|query| Ok::<_, String>(query.to_uppercase()))
+        .unwrap();
+    assert_eq!(output, "Hello, NAME!");
+}
+
+#[test]
+fn parse_error_round_trip() {
+    let error = simple_curly_braces()
+        .eager_parse::<Vec<_>>("Hello } world")
+        .unwrap_err();
+    let json = serde_json::to_string(&error).unwrap();
+    dbg!(&json);
+    let deserialized: lazy_template::simple_curly_braces::EagerParseError =
+        serde_json::from_str(&json).unwrap();
+    assert!(matches!(
+        &deserialized,
+        enclosed::ParseError::ParseQuery(simple_query::ParseError::UnexpectedToken(token, _))
+            if token == "}",
+    ));
+    assert_eq!(deserialized.span(), error.span());
+    assert_eq!(deserialized.to_string(), error.to_string());
+}
+
+#[test]
+fn block_parse_error_round_trip() {
+    let system = Parser::curly_braces()
+        .with_escape_parser(SimpleEscapeParser)
+        .with_query_parser(SimpleQueryParser)
+        .pipe(BlockParser::new)
+        .into_template_system::<&str>();
+    let error = system.eager_parse::<Vec<_>>("a{/if}").unwrap_err();
+    let json = serde_json::to_string(&error).unwrap();
+    dbg!(&json);
+    let deserialized: block::ParseError<
+        enclosed::ParseError<simple_escape::ParseError, simple_query::ParseError>,
+    > = serde_json::from_str(&json).unwrap();
+    drop(json);
+    assert!(matches!(
+        &deserialized,
+        block::ParseError::UnexpectedTag(tag, _) if tag == "/if",
+    ));
+    assert_eq!(deserialized.span(), error.span());
+    assert_eq!(deserialized.to_string(), error.to_string());
+}
@@ -61,7 +61,7 @@ fn reject_invalid_escapes() {
     assert!(matches!(
         error,
         TemplateApplicationError::Parse(enclosed::ParseError::ParseEscape(
-            simple_escape::ParseError::UnsupportedEscapeCode('a'),
+            simple_escape::ParseError::UnsupportedEscapeCode('a', _),
         )),
     ));
 }
@@ -86,7 +86,7 @@ fn reject_unexpected_end_of_input() {
     assert!(matches!(
         error,
         TemplateApplicationError::Parse(enclosed::ParseError::ParseEscape(
-            simple_escape::ParseError::UnexpectedEndOfInput,
+            simple_escape::ParseError::UnexpectedEndOfInput(_),
         )),
     ));
 }
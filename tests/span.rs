@@ -0,0 +1,103 @@
+#![cfg(feature = "std")]
+use lazy_template::{
+    enclosed::{self, simple_escape, simple_query, Segment},
+    simple_curly_braces, GetSpan, Position, Span, TemplateApplicationError,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn segment_spans() {
+    let system = simple_curly_braces();
+    let parsed_template = system.eager_parse::<Vec<_>>("a{foo}\n\\né").unwrap();
+    let actual: Vec<_> = parsed_template
+        .segments()
+        .map(|segment| (segment.span().start, segment.span().end))
+        .collect();
+    dbg!(&actual);
+    let position = |offset, line, column| Position {
+        offset,
+        line,
+        column,
+    };
+    let expected = [
+        (position(0, 1, 1), position(1, 1, 2)),
+        (position(1, 1, 2), position(6, 1, 7)),
+        (position(6, 1, 7), position(7, 2, 1)),
+        (position(7, 2, 1), position(9, 2, 3)),
+        (position(9, 2, 3), position(11, 2, 4)),
+    ];
+    assert_eq!(actual, expected);
+    assert!(matches!(
+        parsed_template.segments().nth(1),
+        Some(Segment::Expression("foo", _)),
+    ));
+}
+
+#[test]
+fn unexpected_end_of_query() {
+    let error = simple_curly_braces()
+        .eager_parse::<Vec<_>>("foo\n{bar")
+        .unwrap_err();
+    dbg!(&error);
+    assert!(matches!(
+        error,
+        enclosed::ParseError::ParseQuery(simple_query::ParseError::UnexpectedEndOfInput(_)),
+    ));
+    let expected = Span::empty(Position {
+        offset: 8,
+        line: 2,
+        column: 5,
+    });
+    assert_eq!(error.span(), expected);
+    assert_eq!(error.span().to_string(), "2:5");
+}
+
+#[test]
+fn unexpected_closing_bracket() {
+    let error = simple_curly_braces()
+        .eager_parse::<Vec<_>>("{foo} }")
+        .unwrap_err();
+    dbg!(&error);
+    let expected = Span {
+        start: Position {
+            offset: 6,
+            line: 1,
+            column: 7,
+        },
+        end: Position {
+            offset: 7,
+            line: 1,
+            column: 8,
+        },
+    };
+    assert_eq!(error.span(), expected);
+}
+
+#[test]
+fn unsupported_escape_code() {
+    let error = simple_curly_braces()
+        .lazy_parse("ab\n\\z")
+        .to_string(|_| Ok::<_, ()>(""))
+        .unwrap_err();
+    dbg!(&error);
+    let TemplateApplicationError::Parse(error) = error else {
+        panic!("expecting a parse error");
+    };
+    assert!(matches!(
+        error,
+        enclosed::ParseError::ParseEscape(simple_escape::ParseError::UnsupportedEscapeCode('z', _)),
+    ));
+    let expected = Span {
+        start: Position {
+            offset: 4,
+            line: 2,
+            column: 2,
+        },
+        end: Position {
+            offset: 5,
+            line: 2,
+            column: 3,
+        },
+    };
+    assert_eq!(error.span(), expected);
+}
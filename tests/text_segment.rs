@@ -0,0 +1,50 @@
+#![cfg(feature = "std")]
+use lazy_template::{enclosed::Segment, simple_curly_braces};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn coalesce_literal_characters() {
+    let system = simple_curly_braces();
+    let parsed_template = system
+        .eager_parse::<Vec<_>>(r"Hello, {name}! \{not a query\} Bye.")
+        .unwrap();
+    let actual: Vec<_> = parsed_template
+        .segments()
+        .map(|segment| match *segment {
+            Segment::Character(char, _) => format!("Character({char:?})"),
+            Segment::Text(text, _) => format!("Text({text:?})"),
+            Segment::Expression(query, _) => format!("Expression({query:?})"),
+        })
+        .collect();
+    dbg!(&actual);
+    let expected = [
+        r#"Text("Hello, ")"#,
+        r#"Expression("name")"#,
+        r#"Text("! ")"#,
+        r#"Text("{")"#,
+        r#"Text("not a query")"#,
+        r#"Text("}")"#,
+        r#"Text(" Bye.")"#,
+    ];
+    assert_eq!(actual, expected);
+    let output = parsed_template
+        .to_template()
+        .to_string(|query| (query == "name").then_some("Alice").ok_or(()))
+        .unwrap();
+    assert_eq!(output, "Hello, Alice! {not a query} Bye.");
+}
+
+#[test]
+fn stop_text_before_syntax_error() {
+    let mut output = String::new();
+    let error = simple_curly_braces()
+        .lazy_parse("abc } def")
+        .write_to(&mut output, |_| Ok::<_, String>(""))
+        .unwrap_err();
+    dbg!(&error);
+    assert_eq!(
+        error.to_string(),
+        r#"Failed to parse query: Unexpected token "}""#
+    );
+    assert_eq!(output, "abc ");
+}